@@ -9,3 +9,166 @@ fn bench(b: &mut Bencher) {
     let f = &include_bytes!("../assets/colorful_studio_2k.hdr")[..];
     b.iter(|| radiant::load(f).unwrap());
 }
+
+#[bench]
+fn bench_progress(b: &mut Bencher) {
+    let f = &include_bytes!("../assets/colorful_studio_2k.hdr")[..];
+    b.iter(|| radiant::load_with_progress(f, |_| {}).unwrap());
+}
+
+/// A 2048x1024 image with the adaptive run-length encoding.
+fn encoded_rle() -> Vec<u8> {
+    let (width, height) = (2048, 1024);
+    let data = (0..width * height)
+        .map(|i| radiant::RGB::splat((i % 37) as f32 * 0.1))
+        .collect();
+    let image = radiant::Image::new(width, height, data).unwrap();
+    let mut bytes = Vec::new();
+    radiant::save_rle(&image, &mut bytes).unwrap();
+    bytes
+}
+
+#[bench]
+fn bench_decoder_read_scanlines(b: &mut Bencher) {
+    let bytes = encoded_rle();
+    b.iter(|| {
+        let mut decoder = radiant::Decoder::new(&bytes[..]).unwrap();
+        let mut row = vec![radiant::RGB::default(); decoder.scanline_len()];
+        while decoder.rows_remaining() > 0 {
+            decoder.read_scanline(&mut row).unwrap();
+        }
+    });
+}
+
+#[bench]
+fn bench_decoder_skip_scanlines(b: &mut Bencher) {
+    let bytes = encoded_rle();
+    b.iter(|| {
+        let mut decoder = radiant::Decoder::new(&bytes[..]).unwrap();
+        decoder.skip_scanlines(decoder.rows_remaining()).unwrap();
+    });
+}
+
+#[bench]
+fn bench_load_rle(b: &mut Bencher) {
+    let bytes = encoded_rle();
+    b.iter(|| radiant::load(&bytes[..]).unwrap());
+}
+
+#[bench]
+fn bench_load_with_rgb(b: &mut Bencher) {
+    let bytes = encoded_rle();
+    b.iter(|| radiant::load_with::<radiant::RGB, _>(&bytes[..]).unwrap());
+}
+
+#[bench]
+fn bench_load_rgba_f32(b: &mut Bencher) {
+    let bytes = encoded_rle();
+    b.iter(|| radiant::load_rgba_f32(&bytes[..], 1.0).unwrap());
+}
+
+#[bench]
+fn bench_load_then_rgba_f32(b: &mut Bencher) {
+    let bytes = encoded_rle();
+    b.iter(|| {
+        let image = radiant::load(&bytes[..]).unwrap();
+        let rgba: Vec<f32> = image
+            .data
+            .iter()
+            .flat_map(|pixel| [pixel.r, pixel.g, pixel.b, 1.0])
+            .collect();
+        rgba
+    });
+}
+
+#[bench]
+fn bench_load_4k(b: &mut Bencher) {
+    let (width, height) = (3840, 2160);
+    let data = (0..width * height)
+        .map(|i| radiant::RGB::splat((i % 1009) as f32 * 0.01))
+        .collect();
+    let image = radiant::Image::new(width, height, data).unwrap();
+    let mut bytes = Vec::new();
+    radiant::save_rle(&image, &mut bytes).unwrap();
+    b.bytes = (width * height * std::mem::size_of::<radiant::RGB>()) as u64;
+    b.iter(|| radiant::load(&bytes[..]).unwrap());
+}
+
+/// A 64x64 image with the adaptive run-length encoding.
+fn encoded_tile() -> Vec<u8> {
+    let data = (0..64 * 64)
+        .map(|i| radiant::RGB::splat((i % 37) as f32 * 0.1))
+        .collect();
+    let image = radiant::Image::new(64, 64, data).unwrap();
+    let mut bytes = Vec::new();
+    radiant::save_rle(&image, &mut bytes).unwrap();
+    bytes
+}
+
+#[bench]
+fn bench_load_tiles(b: &mut Bencher) {
+    let bytes = encoded_tile();
+    b.iter(|| {
+        for _ in 0..1000 {
+            radiant::load(&bytes[..]).unwrap();
+        }
+    });
+}
+
+#[bench]
+fn bench_decode_context_tiles(b: &mut Bencher) {
+    let bytes = encoded_tile();
+    let mut context = radiant::DecodeContext::new();
+    let mut data = Vec::new();
+    b.iter(|| {
+        for _ in 0..1000 {
+            context.load_into(&bytes[..], &mut data).unwrap();
+        }
+    });
+}
+
+/// 1x1 images with headers like the ones different tools write.
+fn header_corpus() -> Vec<Vec<u8>> {
+    let headers: [&[u8]; 4] = [
+        b"#?RADIANCE\n\n",
+        b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n",
+        b"#?RGBE\n# Made with a game engine baker\nGAMMA=1.0\nEXPOSURE=1.0\n\
+          FORMAT=32-bit_rle_rgbe\n\n",
+        b"#?RADIANCE\noconv materials.rad scene.rad\n\
+          rpict -vtv -vp 0 0 1.5 -vd 0 1 0 -vu 0 0 1 -vh 60 -vv 45 -ab 2 -x 512 -y 512\n\
+          VIEW= -vtv -vp 0 0 1.5 -vd 0 1 0 -vu 0 0 1 -vh 60 -vv 45\n\
+          SOFTWARE= RADIANCE 5.4a lastmod Mon Jan 01 2024\n\
+          CAPDATE= 2024:01:02 03:04:05\nGMT= 2024:01:02 03:04:05\n\
+          FORMAT=32-bit_rle_rgbe\n\
+          pfilt -x /2 -y /2\nEXPOSURE=0.5\nPIXASPECT=1\n\
+          PRIMARIES=0.64 0.33 0.3 0.6 0.15 0.06 0.3127 0.329\n\n",
+    ];
+    headers
+        .iter()
+        .map(|header| {
+            let mut bytes = header.to_vec();
+            bytes.extend_from_slice(b"-Y 1 +X 1\n\xff\x00\xff\x80");
+            bytes
+        })
+        .collect()
+}
+
+#[bench]
+fn bench_load_headers(b: &mut Bencher) {
+    let corpus = header_corpus();
+    b.iter(|| {
+        for bytes in &corpus {
+            radiant::load(&bytes[..]).unwrap();
+        }
+    });
+}
+
+#[bench]
+fn bench_load_headers_captured(b: &mut Bencher) {
+    let corpus = header_corpus();
+    b.iter(|| {
+        for bytes in &corpus {
+            radiant::load_with_header(&bytes[..]).unwrap();
+        }
+    });
+}
@@ -12,11 +12,6 @@ struct Options {
     pub image_path: PathBuf,
 }
 
-fn map_channel(v: f32) -> u32 {
-    const COEFF: f32 = 127f32;
-    u32::min(255, (2f32 * (v * COEFF)) as u32)
-}
-
 fn main() -> anyhow::Result<()> {
     let options = Options::from_args();
     let f = File::open(&options.image_path).context("Failed to open specified file")?;
@@ -24,14 +19,9 @@ fn main() -> anyhow::Result<()> {
     let image = radiant::load(f).context("Failed to load image data")?;
 
     let buf: Vec<_> = image
-        .data
-        .iter()
-        .map(|px| {
-            let r = map_channel(px.r);
-            let g = map_channel(px.g);
-            let b = map_channel(px.b);
-            0xFF_00_00_00u32 | r << 16 | g << 8 | b
-        })
+        .tone_map_reinhard(0.18)
+        .into_iter()
+        .map(|[r, g, b]| 0xFF_00_00_00u32 | u32::from(r) << 16 | u32::from(g) << 8 | u32::from(b))
         .collect();
 
     let width = image.width as usize;
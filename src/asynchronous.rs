@@ -0,0 +1,246 @@
+//! The asynchronous counterparts of the readers in the crate root.
+//!
+//! Reading a byte is a different operation here, so the loops that undo run-length encoding are
+//! repeated rather than shared. Everything that does not read, like the RLE markers, the lengths
+//! of runs and the channels of each pixel, comes from the synchronous code.
+
+use super::{
+    check_magic, dim_parser, old_pixel, DecodedPixel, Header, Image, Limit, LoadError, LoadLimits,
+    LoadResult, OldRun, MAGIC_PREFIX, NEW_RLE_LEN, RGB, RGBE,
+};
+use dim_parser::{CR, EOL};
+use std::io::Error as IoError;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt};
+
+async fn read_rgbe<R: AsyncBufRead + Unpin>(reader: &mut R) -> std::io::Result<RGBE> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf).await?;
+    Ok(buf.into())
+}
+
+/// Read a line of the header into `buf`, without its line ending, taking its length from
+/// `remaining` and returning the line ending. Lines may end with LF, CR LF or just CR, and are
+/// limited like in the synchronous parser.
+async fn read_line<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    remaining: &mut usize,
+) -> LoadResult<&'static [u8]> {
+    let max_len = LoadLimits::header_only().max_header_line_bytes;
+    buf.clear();
+    loop {
+        let available = reader.fill_buf().await?;
+        if available.is_empty() {
+            return Err(LoadError::Eof(IoError::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "header ended unexpectedly",
+            )));
+        }
+
+        match available.iter().position(|&b| b == EOL || b == CR) {
+            Some(i) => {
+                let cr = available[i] == CR;
+                buf.extend_from_slice(&available[..i]);
+                reader.consume(i + 1);
+                if buf.len() > max_len {
+                    return Err(LoadError::LimitExceeded(Limit::HeaderLineBytes));
+                }
+                let ending: &[u8] = if !cr {
+                    b"\n"
+                } else if reader.fill_buf().await?.first() == Some(&EOL) {
+                    reader.consume(1);
+                    b"\r\n"
+                } else {
+                    b"\r"
+                };
+                // Every line ending counts as one byte
+                *remaining = remaining
+                    .checked_sub(buf.len() + 1)
+                    .ok_or(LoadError::LimitExceeded(Limit::HeaderBytes))?;
+                return Ok(ending);
+            }
+            None => {
+                let len = available.len();
+                buf.extend_from_slice(available);
+                reader.consume(len);
+            }
+        }
+        if buf.len() > max_len {
+            return Err(LoadError::LimitExceeded(Limit::HeaderLineBytes));
+        }
+    }
+}
+
+async fn parse_header<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+) -> LoadResult<(Header, dim_parser::Resolution)> {
+    let mut header = Header::default();
+    let mut line = Vec::new();
+    // The magic has already been read
+    let mut remaining = LoadLimits::header_only().max_header_bytes - MAGIC_PREFIX.len();
+
+    read_line(reader, &mut line, &mut remaining).await?;
+    header.program = Some(dim_parser::parse_program(&line));
+
+    // The header ends with an empty line
+    loop {
+        let ending = read_line(reader, &mut line, &mut remaining).await?;
+        if line.is_empty() {
+            break;
+        }
+        header.push_line(&line);
+        header.raw_bytes.extend_from_slice(ending);
+    }
+    header.check_format()?;
+
+    // Buffer the resolution line so the synchronous parser can handle it, skipping any more empty
+    // lines before it
+    loop {
+        read_line(reader, &mut line, &mut remaining).await?;
+        if !line.is_empty() {
+            break;
+        }
+    }
+    let resolution = dim_parser::expect_resolution_line(&line)?;
+    Ok((header, resolution))
+}
+
+async fn old_decrunch<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    scanline: &mut [RGB],
+    mut x: usize,
+    run: &mut OldRun,
+) -> LoadResult {
+    while x < scanline.len() {
+        let rgbe = read_rgbe(reader).await?;
+        x = old_pixel(rgbe, scanline, x, run)?;
+    }
+
+    Ok(())
+}
+
+async fn decrunch_channel<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    mut scanline: &mut [RGB],
+    mutate_pixel: fn(&mut RGB, u8),
+) -> LoadResult {
+    while !scanline.is_empty() {
+        let code = reader.read_u8().await? as usize;
+        if code > 128 {
+            // run
+            let count = code & 127;
+            let pixels = scanline.get_mut(..count).ok_or(LoadError::Rle)?;
+
+            let val = reader.read_u8().await?;
+            for pixel in pixels {
+                mutate_pixel(pixel, val);
+            }
+            scanline = &mut scanline[count..];
+        } else {
+            // non-run
+            let mut bytes_left = code;
+            while bytes_left > 0 {
+                let buf = reader.fill_buf().await?;
+
+                if buf.is_empty() {
+                    return Err(LoadError::Eof(IoError::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "failed to fill whole buffer",
+                    )));
+                }
+
+                let count = buf.len().min(bytes_left);
+                let pixels = scanline.get_mut(..count).ok_or(LoadError::Rle)?;
+
+                for (pixel, &val) in pixels.iter_mut().zip(&buf[..count]) {
+                    mutate_pixel(pixel, val);
+                }
+                scanline = &mut scanline[count..];
+                reader.consume(count);
+                bytes_left -= count;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn decrunch<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    scanline: &mut [RGB],
+    run: &mut OldRun,
+) -> LoadResult {
+    decrunch_scanline(reader, scanline, run).await?;
+    run.end_scanline(scanline.len());
+    Ok(())
+}
+
+async fn decrunch_scanline<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    scanline: &mut [RGB],
+    run: &mut OldRun,
+) -> LoadResult {
+    let carried = run.start_scanline(scanline.len());
+    if let Some(last) = run.last.filter(|_| carried > 0) {
+        let from = RGB::from(last);
+        scanline[..carried].iter_mut().for_each(|to| *to = from);
+        return old_decrunch(reader, scanline, carried, run).await;
+    }
+
+    let rgbe = read_rgbe(reader).await?;
+
+    if !NEW_RLE_LEN.contains(&scanline.len()) || !rgbe.is_new_decrunch_marker() {
+        let x = old_pixel(rgbe, scanline, 0, run)?;
+        return old_decrunch(reader, scanline, x, run).await;
+    }
+    rgbe.check_scanline_len(scanline.len())?;
+    run.reset();
+
+    for mutate_pixel in RGB::CHANNELS {
+        decrunch_channel(reader, scanline, mutate_pixel).await?;
+    }
+
+    Ok(())
+}
+
+/// Load a Radiance HDR image from a reader that implements [`AsyncBufRead`].
+///
+/// This behaves exactly like [`load`](super::load), but awaits the reader instead of blocking
+/// on it.
+pub async fn load_async<R: AsyncBufRead + Unpin>(mut reader: R) -> LoadResult<Image> {
+    let mut buf = [0u8; MAGIC_PREFIX.len()];
+    reader.read_exact(&mut buf).await?;
+    check_magic(&buf)?;
+
+    // Grab image dimensions
+    let (header, resolution) = parse_header(&mut reader).await?;
+    let dim_parser::Resolution {
+        width,
+        height,
+        orientation,
+    } = resolution;
+
+    let length = width.checked_mul(height).ok_or(LoadError::FileFormat)?;
+
+    // Allocate result buffer
+    let mut data = vec![RGB::default(); length];
+
+    if length > 0 {
+        // Decrunch image data
+        let scanline_len = orientation.scanline_len(width, height);
+        let mut run = OldRun::new(length);
+        for scanline in data.chunks_mut(scanline_len) {
+            decrunch(&mut reader, scanline, &mut run).await?;
+        }
+        orientation.normalize(&mut data, width, height);
+    }
+
+    Ok(Image {
+        width,
+        height,
+        orientation,
+        color_format: header.format.unwrap_or_default(),
+        pixel_aspect: header.pixel_aspect.unwrap_or(1.0),
+        data,
+    })
+}
@@ -0,0 +1,180 @@
+use super::RGB;
+
+/// The luminous efficacy Radiance assumes for white light, in lumens per watt.
+///
+/// Pixel values, whether RGB or the X, Y and Z of XYZE files, are radiances in watts per
+/// steradian per square meter. Multiplying a [luminance](RGB::luminance) or a Y value by this
+/// gives the luminance in candela per square meter. Nothing in this crate applies it for you.
+pub const WHITE_EFFICACY: f32 = 179.0;
+
+/// A 3x3 matrix that converts between color spaces, applied to column vectors.
+pub type ColorMatrix = [[f32; 3]; 3];
+
+/// The CIE (x, y) chromaticities of the primaries and white point of an RGB color space, as
+/// given by the `PRIMARIES` variable in the header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Primaries {
+    /// The chromaticity of the red primary.
+    pub red: [f32; 2],
+    /// The chromaticity of the green primary.
+    pub green: [f32; 2],
+    /// The chromaticity of the blue primary.
+    pub blue: [f32; 2],
+    /// The chromaticity of the white point.
+    pub white: [f32; 2],
+}
+
+impl Default for Primaries {
+    /// The primaries Radiance assumes when a file does not specify any.
+    fn default() -> Self {
+        Self {
+            red: [0.640, 0.330],
+            green: [0.290, 0.600],
+            blue: [0.150, 0.060],
+            white: [0.3333, 0.3333],
+        }
+    }
+}
+
+impl Primaries {
+    /// The primaries and D65 white point of Rec. 709, which sRGB shares.
+    pub const REC709: Self = Self {
+        red: [0.640, 0.330],
+        green: [0.300, 0.600],
+        blue: [0.150, 0.060],
+        white: [0.3127, 0.3290],
+    };
+
+    /// The matrix that converts RGB values in this color space to CIE XYZ. The white point maps
+    /// to a Y of 1.
+    pub fn rgb_to_xyz(&self) -> ColorMatrix {
+        to_f32(&self.rgb_to_xyz_f64())
+    }
+
+    /// The matrix that converts CIE XYZ values to RGB values in this color space.
+    pub fn xyz_to_rgb(&self) -> ColorMatrix {
+        to_f32(&invert(&self.rgb_to_xyz_f64()))
+    }
+
+    /// The matrix that converts RGB values in this color space to RGB values in the `target` color
+    /// space. If the white points differ, colors are adapted with the Bradford transform, so that
+    /// white in this color space stays white in the target.
+    pub fn conversion_to(&self, target: &Primaries) -> ColorMatrix {
+        let to_xyz = product(
+            &adaptation(self.white, target.white),
+            &self.rgb_to_xyz_f64(),
+        );
+        to_f32(&product(&invert(&target.rgb_to_xyz_f64()), &to_xyz))
+    }
+
+    fn rgb_to_xyz_f64(&self) -> [[f64; 3]; 3] {
+        let [r, g, b] = [self.red, self.green, self.blue].map(white_xyz);
+        let primaries = [[r[0], g[0], b[0]], [r[1], g[1], b[1]], [r[2], g[2], b[2]]];
+
+        // Scale each primary so that together they add up to the white point
+        let scale = multiply(&invert(&primaries), white_xyz(self.white));
+        primaries.map(|row| [row[0] * scale[0], row[1] * scale[1], row[2] * scale[2]])
+    }
+}
+
+/// The white point of CIE XYZ, where X, Y and Z are equal. Radiance uses it for XYZE files.
+const EQUAL_ENERGY_WHITE: [f32; 2] = [1.0 / 3.0, 1.0 / 3.0];
+
+/// The matrix that converts CIE XYZ values, as stored in XYZE files, to RGB values in the
+/// `target` color space, adapting the white point like [`Primaries::conversion_to`] does.
+pub(crate) fn xyz_conversion_to(target: &Primaries) -> ColorMatrix {
+    let adapted = adaptation(EQUAL_ENERGY_WHITE, target.white);
+    to_f32(&product(&invert(&target.rgb_to_xyz_f64()), &adapted))
+}
+
+/// The XYZ of a chromaticity with a Y of 1.
+fn white_xyz([x, y]: [f32; 2]) -> [f64; 3] {
+    let [x, y] = [f64::from(x), f64::from(y)];
+    [x / y, 1.0, (1.0 - x - y) / y]
+}
+
+/// The Bradford chromatic adaptation from one white point to another, in CIE XYZ.
+fn adaptation(from: [f32; 2], to: [f32; 2]) -> [[f64; 3]; 3] {
+    const BRADFORD: [[f64; 3]; 3] = [
+        [0.8951, 0.2664, -0.1614],
+        [-0.7502, 1.7135, 0.0367],
+        [0.0389, -0.0685, 1.0296],
+    ];
+    if from == to {
+        return [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    }
+
+    // Scale the cone responses of one white point to those of the other
+    let from = multiply(&BRADFORD, white_xyz(from));
+    let to = multiply(&BRADFORD, white_xyz(to));
+    let scaled = [0, 1, 2].map(|i| BRADFORD[i].map(|v| v * to[i] / from[i]));
+    product(&invert(&BRADFORD), &scaled)
+}
+
+fn to_f32(matrix: &[[f64; 3]; 3]) -> ColorMatrix {
+    matrix.map(|row| row.map(|v| v as f32))
+}
+
+fn multiply(matrix: &[[f64; 3]; 3], vector: [f64; 3]) -> [f64; 3] {
+    matrix.map(|row| row[0] * vector[0] + row[1] * vector[1] + row[2] * vector[2])
+}
+
+fn product(a: &[[f64; 3]; 3], b: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    a.map(|row| [0, 1, 2].map(|c| row[0] * b[0][c] + row[1] * b[1][c] + row[2] * b[2][c]))
+}
+
+fn invert(m: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let cofactor =
+        |r0: usize, r1: usize, c0: usize, c1: usize| m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+    let adjugate = [
+        [
+            cofactor(1, 2, 1, 2),
+            -cofactor(0, 2, 1, 2),
+            cofactor(0, 1, 1, 2),
+        ],
+        [
+            -cofactor(1, 2, 0, 2),
+            cofactor(0, 2, 0, 2),
+            -cofactor(0, 1, 0, 2),
+        ],
+        [
+            cofactor(1, 2, 0, 1),
+            -cofactor(0, 2, 0, 1),
+            cofactor(0, 1, 0, 1),
+        ],
+    ];
+    let determinant =
+        m[0][0] * adjugate[0][0] + m[0][1] * adjugate[1][0] + m[0][2] * adjugate[2][0];
+    adjugate.map(|row| row.map(|v| v / determinant))
+}
+
+impl RGB {
+    /// The luminance of this pixel, using the weights Radiance uses for its default primaries.
+    pub fn luminance(&self) -> f32 {
+        0.265 * self.r + 0.670 * self.g + 0.065 * self.b
+    }
+
+    /// Apply a color matrix to this pixel, treating it as a column vector.
+    pub fn transform(&self, matrix: &ColorMatrix) -> [f32; 3] {
+        matrix.map(|row| row[0] * self.r + row[1] * self.g + row[2] * self.b)
+    }
+
+    /// Convert to CIE XYZ, assuming the default Radiance primaries.
+    ///
+    /// This computes the conversion matrix on every call. When converting many pixels, get the
+    /// matrix once from [`Primaries::rgb_to_xyz`] and use [`transform`](Self::transform).
+    pub fn to_xyz(&self) -> [f32; 3] {
+        self.transform(&Primaries::default().rgb_to_xyz())
+    }
+
+    /// Convert from CIE XYZ, assuming the default Radiance primaries.
+    pub fn from_xyz(xyz: [f32; 3]) -> Self {
+        let [r, g, b] = RGB {
+            r: xyz[0],
+            g: xyz[1],
+            b: xyz[2],
+        }
+        .transform(&Primaries::default().xyz_to_rgb());
+        Self { r, g, b }
+    }
+}
@@ -0,0 +1,63 @@
+use super::{decode_into, read_preamble_with, Image, LoadLimits, LoadResult, RGB};
+use std::io::BufRead;
+
+/// Buffers that are kept between loads, so that decoding many images allocates less.
+///
+/// Each thread decoding images can own one. [`load_into`](Self::load_into) reuses a pixel buffer
+/// as well, so that it does not allocate at all once the buffers are large enough, as long as
+/// every header line fits in the buffer of the reader.
+#[derive(Debug, Default)]
+pub struct DecodeContext {
+    scanline: Vec<RGB>,
+}
+
+impl DecodeContext {
+    /// Create a context with no buffers allocated yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a Radiance HDR image from a reader that implements [`BufRead`], like
+    /// [`load`](super::load).
+    pub fn load<R: BufRead>(&mut self, reader: R) -> LoadResult<Image> {
+        let (header, resolution, reader) =
+            read_preamble_with(reader, &LoadLimits::header_only(), false)?;
+        let mut data = Vec::new();
+        decode_into(
+            reader,
+            resolution,
+            &LoadLimits::none(),
+            &mut data,
+            &mut self.scanline,
+            |_, _| Ok(()),
+        )?;
+        Ok(Image {
+            width: resolution.width,
+            height: resolution.height,
+            orientation: resolution.orientation,
+            color_format: header.format.unwrap_or_default(),
+            pixel_aspect: header.pixel_aspect.unwrap_or(1.0),
+            data,
+        })
+    }
+
+    /// Load a Radiance HDR image from a reader that implements [`BufRead`] into an existing
+    /// buffer, like [`load_into`](super::load_into), returning its width and height.
+    pub fn load_into<R: BufRead>(
+        &mut self,
+        reader: R,
+        data: &mut Vec<RGB>,
+    ) -> LoadResult<(usize, usize)> {
+        let (_, resolution, reader) =
+            read_preamble_with(reader, &LoadLimits::header_only(), false)?;
+        decode_into(
+            reader,
+            resolution,
+            &LoadLimits::none(),
+            data,
+            &mut self.scanline,
+            |_, _| Ok(()),
+        )?;
+        Ok((resolution.width, resolution.height))
+    }
+}
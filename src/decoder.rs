@@ -0,0 +1,201 @@
+use super::{
+    decrunch, dim_parser, read_preamble, skip_scanline, CountingReader, DecodeProgress, Header,
+    LoadError, LoadResult, OldRun, Orientation, RGB,
+};
+use std::io::BufRead;
+use std::ops::ControlFlow;
+
+/// A decoder that reads a Radiance HDR image one scanline at a time, into buffers provided by the
+/// caller.
+///
+/// This avoids holding the whole image in memory. Scanlines come in the order they are stored,
+/// so unlike [`load`](super::load) they are not rearranged according to the
+/// [`Orientation`]. For the standard orientation, each scanline is a row, from top to bottom.
+///
+/// ```
+/// let bytes = b"#?RADIANCE\n\n-Y 2 +X 1\n\xff\x00\xff\x80\x00\xff\x00\x80";
+/// let mut decoder = radiant::Decoder::new(&bytes[..])?;
+/// let mut row = vec![Default::default(); decoder.width()];
+/// while decoder.next_row(&mut row)? {
+///     // Draw the row
+/// }
+/// # Ok::<(), radiant::LoadError>(())
+/// ```
+#[derive(Debug)]
+pub struct Decoder<R> {
+    reader: CountingReader<R>,
+    header: Header,
+    resolution: dim_parser::Resolution,
+    rows_done: usize,
+    run: OldRun,
+}
+
+impl<R: BufRead> Decoder<R> {
+    /// Read the header of an image from a reader that implements [`BufRead`], leaving it at the
+    /// start of the pixel data.
+    pub fn new(reader: R) -> LoadResult<Self> {
+        let reader = CountingReader {
+            inner: reader,
+            count: 0,
+        };
+        let (header, resolution, reader) = read_preamble(reader)?;
+        let length = resolution
+            .width
+            .checked_mul(resolution.height)
+            .ok_or(LoadError::FileFormat)?;
+        Ok(Self {
+            reader,
+            header,
+            resolution,
+            rows_done: 0,
+            run: OldRun::new(length),
+        })
+    }
+
+    /// The width of the image, in pixels.
+    pub fn width(&self) -> usize {
+        self.resolution.width
+    }
+
+    /// The height of the image, in pixels.
+    pub fn height(&self) -> usize {
+        self.resolution.height
+    }
+
+    /// The order the scanlines are stored in.
+    pub fn orientation(&self) -> Orientation {
+        self.resolution.orientation
+    }
+
+    /// The information in the header.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// The number of pixels in each scanline. This is the width, unless the
+    /// [`orientation`](Self::orientation) stores the image in columns.
+    pub fn scanline_len(&self) -> usize {
+        let dim_parser::Resolution {
+            width,
+            height,
+            orientation,
+        } = self.resolution;
+        orientation.scanline_len(width, height)
+    }
+
+    /// How many scanlines are left to decode.
+    pub fn rows_remaining(&self) -> usize {
+        self.resolution.scanlines() - self.rows_done
+    }
+
+    /// How far along decoding is.
+    pub fn progress(&self) -> DecodeProgress {
+        DecodeProgress {
+            rows_done: self.rows_done,
+            rows_total: self.resolution.scanlines(),
+            bytes_read: self.reader.count,
+        }
+    }
+
+    /// Decode the next scanline into `scanline`, which must hold exactly
+    /// [`scanline_len`](Self::scanline_len) pixels.
+    ///
+    /// Returns [`LoadError::BufferSize`] if it does not, and [`LoadError::PastEnd`] if every
+    /// scanline has already been decoded. If decoding fails partway through a scanline, the
+    /// decoder should be dropped.
+    pub fn read_scanline(&mut self, scanline: &mut [RGB]) -> LoadResult {
+        if self.rows_remaining() == 0 {
+            return Err(LoadError::PastEnd);
+        }
+
+        let expected = self.scanline_len();
+        if scanline.len() != expected {
+            return Err(LoadError::BufferSize {
+                expected,
+                actual: scanline.len(),
+            });
+        }
+
+        decrunch(&mut self.reader, scanline, &mut self.run)?;
+        self.rows_done += 1;
+        Ok(())
+    }
+
+    /// Like [`read_scanline`](Self::read_scanline), but return `false` instead of an error once
+    /// every scanline has been decoded, so that it can drive a `while` loop.
+    pub fn next_row(&mut self, scanline: &mut [RGB]) -> LoadResult<bool> {
+        if self.rows_remaining() == 0 {
+            return Ok(false);
+        }
+        self.read_scanline(scanline)?;
+        Ok(true)
+    }
+
+    /// Move past the next `n` scanlines without decoding them into pixels.
+    ///
+    /// This is cheaper than decoding them into a buffer that is thrown away, but the run-length
+    /// encoding is still checked. Returns [`LoadError::PastEnd`] without skipping anything if
+    /// there are fewer than `n` scanlines left.
+    pub fn skip_scanlines(&mut self, n: usize) -> LoadResult {
+        if n > self.rows_remaining() {
+            return Err(LoadError::PastEnd);
+        }
+
+        let len = self.scanline_len();
+        for _ in 0..n {
+            skip_scanline(&mut self.reader, len, &mut self.run)?;
+            self.rows_done += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Decode a Radiance HDR image from a reader that implements [`BufRead`] one scanline at a time,
+/// calling `f` with the index and pixels of each one.
+///
+/// Every scanline is decoded into the same buffer, so memory use is proportional to the width of
+/// the image rather than its area. Scanlines come in the order they are stored, like with
+/// [`Decoder`]. If `f` returns [`ControlFlow::Break`], decoding stops early without an error.
+pub fn for_each_scanline<R, F>(reader: R, mut f: F) -> LoadResult
+where
+    R: BufRead,
+    F: FnMut(usize, &[RGB]) -> ControlFlow<()>,
+{
+    let mut decoder = Decoder::new(reader)?;
+    let mut scanline = vec![RGB::default(); decoder.scanline_len()];
+    let mut index = 0;
+    while decoder.next_row(&mut scanline)? {
+        if f(index, &scanline).is_break() {
+            break;
+        }
+        index += 1;
+    }
+    Ok(())
+}
+
+/// Decode a Radiance HDR image from a reader that implements [`BufRead`] one pixel at a time,
+/// calling `f` with the x and y coordinate and the value of each one, and returning the width
+/// and height.
+///
+/// Like [`for_each_scanline`], only one scanline is held in memory at a time. Coordinates count
+/// from the top left, the same as in the [`Image`](super::Image) returned by
+/// [`load`](super::load), but pixels come in the order they are stored.
+pub fn load_pixels<R, F>(reader: R, mut f: F) -> LoadResult<(usize, usize)>
+where
+    R: BufRead,
+    F: FnMut(usize, usize, RGB),
+{
+    let mut decoder = Decoder::new(reader)?;
+    let (width, height) = (decoder.width(), decoder.height());
+    let orientation = decoder.orientation();
+    let mut scanline = vec![RGB::default(); decoder.scanline_len()];
+    let mut s = 0;
+    while decoder.next_row(&mut scanline)? {
+        for (i, &pixel) in scanline.iter().enumerate() {
+            let (x, y) = orientation.position(s, i, width, height);
+            f(x, y, pixel);
+        }
+        s += 1;
+    }
+    Ok((width, height))
+}
@@ -1,17 +1,460 @@
-use super::{LoadError, LoadResult, ReadExt};
-use std::io::BufRead;
+use super::{
+    header, ColorFormat, Header, Limit, LoadError, LoadLimits, LoadResult, Orientation, ReadExt,
+    Warning, MAGIC_PREFIX,
+};
+use std::io::{BufRead, Error as IoError, ErrorKind, Read};
 
-const EOL: u8 = 0xA;
+pub(crate) const EOL: u8 = 0xA;
+pub(crate) const CR: u8 = 0xD;
 
-pub(crate) fn parse_header<R: BufRead>(mut reader: R) -> LoadResult<(usize, usize, R)> {
-    // Skip first paragraph
+/// The contents of a resolution line.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Resolution {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) orientation: Orientation,
+}
+
+impl Resolution {
+    /// The number of scanlines the pixel data is stored in.
+    pub(crate) fn scanlines(&self) -> usize {
+        if self.width == 0 || self.height == 0 {
+            0
+        } else if self.orientation.is_x_major() {
+            self.width
+        } else {
+            self.height
+        }
+    }
+}
+
+/// Read a line into `buf`, without its line ending, returning the line ending.
+///
+/// Lines may end with LF, CR LF or just CR. A line ending with CR takes the LF after it, if there
+/// is one, so a lone CR at the end of the resolution line must not be followed by pixel data
+/// that starts with an LF.
+///
+/// Fails with [`Limit::HeaderLineBytes`] as soon as the line is longer than `max_len`.
+fn read_line<R: BufRead>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    max_len: usize,
+) -> LoadResult<&'static [u8]> {
+    buf.clear();
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(available) => available,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        if available.is_empty() {
+            return Err(LoadError::Eof(IoError::new(
+                ErrorKind::UnexpectedEof,
+                "header ended unexpectedly",
+            )));
+        }
+
+        match available.iter().position(|&b| b == EOL || b == CR) {
+            Some(i) => {
+                let cr = available[i] == CR;
+                buf.extend_from_slice(&available[..i]);
+                reader.consume(i + 1);
+                if buf.len() > max_len {
+                    return Err(LoadError::LimitExceeded(Limit::HeaderLineBytes));
+                }
+                return line_ending(reader, cr);
+            }
+            None => {
+                let len = available.len();
+                buf.extend_from_slice(available);
+                reader.consume(len);
+            }
+        }
+        if buf.len() > max_len {
+            return Err(LoadError::LimitExceeded(Limit::HeaderLineBytes));
+        }
+    }
+}
+
+/// Consume the LF after a line that ended with a CR, if there is one, returning the whole line
+/// ending.
+fn line_ending<R: BufRead>(reader: &mut R, cr: bool) -> LoadResult<&'static [u8]> {
+    if !cr {
+        Ok(b"\n")
+    } else if reader.fill_buf()?.first() == Some(&EOL) {
+        reader.consume(1);
+        Ok(b"\r\n")
+    } else {
+        Ok(b"\r")
+    }
+}
+
+/// Call `f` with the next line, without its line ending, returning what it returns, the length
+/// of the line and its line ending. Lines end like they do for [`read_line`].
+///
+/// When the whole line is already in the buffer of the reader, `f` is given it from there, so
+/// that nothing is copied. Only lines that span reads are collected in `buf`.
+fn with_line<R: BufRead, T, F: FnOnce(&[u8]) -> T>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    max_len: usize,
+    f: F,
+) -> LoadResult<(T, usize, &'static [u8])> {
+    let available = match reader.fill_buf() {
+        Ok(available) => available,
+        // Let read_line retry
+        Err(e) if e.kind() == ErrorKind::Interrupted => &[],
+        Err(e) => return Err(e.into()),
+    };
+
+    if let Some(i) = available.iter().position(|&b| b == EOL || b == CR) {
+        if i > max_len {
+            return Err(LoadError::LimitExceeded(Limit::HeaderLineBytes));
+        }
+        let cr = available[i] == CR;
+        let result = f(&available[..i]);
+        reader.consume(i + 1);
+        return Ok((result, i, line_ending(reader, cr)?));
+    }
+
+    let ending = read_line(reader, buf, max_len)?;
+    Ok((f(buf), buf.len(), ending))
+}
+
+/// The lines of a header, read while keeping count of how many bytes are left under the limits.
+struct HeaderLines {
+    buf: Vec<u8>,
+    max_line_len: usize,
+    remaining: usize,
+}
+
+impl HeaderLines {
+    fn new(limits: &LoadLimits) -> Self {
+        Self {
+            buf: Vec::new(),
+            max_line_len: limits.max_header_line_bytes,
+            // The magic has already been read
+            remaining: limits.max_header_bytes.saturating_sub(MAGIC_PREFIX.len()),
+        }
+    }
+
+    /// Call `f` with the next line, like [`with_line`], returning what it returns and the line
+    /// ending.
+    fn next<R: BufRead, T, F: FnOnce(&[u8]) -> T>(
+        &mut self,
+        reader: &mut R,
+        f: F,
+    ) -> LoadResult<(T, &'static [u8])> {
+        let (result, len, ending) = with_line(reader, &mut self.buf, self.max_line_len, f)?;
+        self.count(len)?;
+        Ok((result, ending))
+    }
+
+    /// Read the next line into `buf`, like [`read_line`].
+    fn read<R: BufRead>(&mut self, reader: &mut R) -> LoadResult<&'static [u8]> {
+        let ending = read_line(reader, &mut self.buf, self.max_line_len)?;
+        self.count(self.buf.len())?;
+        Ok(ending)
+    }
+
+    fn count(&mut self, len: usize) -> LoadResult {
+        // Every line ending counts as one byte
+        self.remaining = self
+            .remaining
+            .checked_sub(len + 1)
+            .ok_or(LoadError::LimitExceeded(Limit::HeaderBytes))?;
+        Ok(())
+    }
+}
+
+/// The programs that [`load_strict`](super::load_strict) accepts after the `#?` at the start of a
+/// file.
+const PROGRAMS: &[&str] = &["RADIANCE", "RGBE"];
+
+/// Whether `program` is one of [`PROGRAMS`].
+pub(crate) fn is_known_program(program: &str) -> bool {
+    PROGRAMS.contains(&program)
+}
+
+/// The program named by the rest of the first line of a file, after the `#?`, as a string.
+/// Trailing whitespace and NUL bytes, which some writers leave after the name, are ignored.
+pub(crate) fn parse_program(line: &[u8]) -> String {
+    let end = line
+        .iter()
+        .rposition(|&b| b != 0 && !b.is_ascii_whitespace())
+        .map_or(0, |i| i + 1);
+    String::from_utf8_lossy(&line[..end]).into_owned()
+}
+
+/// The variables of a header whose lines are not kept that loading still needs.
+#[derive(Debug, Default)]
+struct Uncaptured {
+    format: LastFormat,
+    pixel_aspect: Option<f32>,
+}
+
+impl Uncaptured {
+    fn push_line(&mut self, line: &[u8]) {
+        let equals = match line.iter().position(|&b| b == b'=') {
+            Some(equals) => equals,
+            None => return,
+        };
+        // A name that is not valid UTF-8 is neither of these
+        let name = match std::str::from_utf8(&line[..equals]) {
+            Ok(name) => name.trim(),
+            Err(_) => return,
+        };
+        if name != "FORMAT" && name != "PIXASPECT" {
+            return;
+        }
+
+        let value = String::from_utf8_lossy(&line[equals + 1..]);
+        let value = value.trim();
+        if name == "FORMAT" {
+            self.format.set(value);
+        } else if let Some(aspect) = header::parse_positive(value) {
+            self.pixel_aspect = Some(self.pixel_aspect.unwrap_or(1.0) * aspect);
+        }
+    }
+
+    /// Fill in the header like [`Header::push_line`] would, checking the format like
+    /// [`Header::check_format`] does.
+    fn finish(self, header: &mut Header) -> LoadResult {
+        header.format = self.format.check()?;
+        header.pixel_aspect = self.pixel_aspect;
+        Ok(())
+    }
+}
+
+/// The last `FORMAT` in a header whose lines are not kept.
+#[derive(Debug, Default)]
+enum LastFormat {
+    #[default]
+    Unset,
+    Known(ColorFormat),
+    Unknown(String),
+}
+
+impl LastFormat {
+    fn set(&mut self, value: &str) {
+        *self = match [ColorFormat::Rgbe, ColorFormat::Xyze]
+            .iter()
+            .copied()
+            .find(|format| format.as_str() == value)
+        {
+            Some(format) => Self::Known(format),
+            None => Self::Unknown(value.to_owned()),
+        };
+    }
+
+    fn check(self) -> LoadResult<Option<ColorFormat>> {
+        match self {
+            Self::Unset => Ok(None),
+            Self::Known(format) => Ok(Some(format)),
+            Self::Unknown(value) if value.is_empty() => Err(LoadError::FileFormat),
+            Self::Unknown(value) => Err(LoadError::UnsupportedFormat(value)),
+        }
+    }
+}
+
+/// Parse the header after the `#?` at the start of a file, failing if it is larger than `limits`
+/// allow.
+///
+/// Unless `capture` is set, only the [`format`](Header::format) and
+/// [`pixel_aspect`](Header::pixel_aspect) of the returned header are filled in. Then nothing is
+/// allocated as long as every line fits in the buffer of the reader.
+pub(crate) fn parse_header<R: BufRead>(
+    mut reader: R,
+    limits: &LoadLimits,
+    capture: bool,
+) -> LoadResult<(Header, Resolution, R)> {
+    let mut header = Header::default();
+    let mut uncaptured = Uncaptured::default();
+    let mut lines = HeaderLines::new(limits);
+
+    header.program = lines
+        .next(&mut reader, |line| capture.then(|| parse_program(line)))?
+        .0;
+
+    // The header ends with an empty line
     loop {
-        let mut next_is_eol = || reader.read_byte().map(|b| b == EOL);
-        if next_is_eol()? && next_is_eol()? {
+        let (end, ending) = lines.next(&mut reader, |line| {
+            if line.is_empty() {
+                return true;
+            }
+            if capture {
+                header.push_line(line);
+            } else {
+                uncaptured.push_line(line);
+            }
+            false
+        })?;
+        if end {
             break;
         }
+        if capture {
+            header.raw_bytes.extend_from_slice(ending);
+        }
     }
+    if capture {
+        header.check_format()?;
+    } else {
+        uncaptured.finish(&mut header)?;
+    }
+
+    // Some writers leave more than one empty line before the resolution
+    let resolution = loop {
+        let (resolution, _) = lines.next(&mut reader, |line| {
+            if line.is_empty() {
+                None
+            } else {
+                Some(expect_resolution_line(line))
+            }
+        })?;
+        if let Some(resolution) = resolution {
+            break resolution?;
+        }
+    };
+    Ok((header, resolution, reader))
+}
+
+/// Variables that Radiance itself may write in a header.
+const KNOWN_VARIABLES: &[&str] = &[
+    "FORMAT",
+    "EXPOSURE",
+    "GAMMA",
+    "COLORCORR",
+    "PRIMARIES",
+    "SOFTWARE",
+    "VIEW",
+    "PIXASPECT",
+    "CAPDATE",
+    "GMT",
+    "FRAME",
+    "LATLONG",
+];
+
+/// Like [`parse_header`], but recover from quirks that are safe to ignore, recording each one in
+/// `warnings`. The header still has to fit in `limits`.
+pub(crate) fn parse_header_lenient<R: BufRead>(
+    mut reader: R,
+    limits: &LoadLimits,
+    warnings: &mut Vec<Warning>,
+) -> LoadResult<(Header, Resolution, R)> {
+    let mut header = Header::default();
+    let mut lines = HeaderLines::new(limits);
+    let mut crlf = false;
+    let mut format_seen = false;
+
+    crlf |= lines.read(&mut reader)? != b"\n";
+    header.program = Some(parse_program(&lines.buf));
+
+    let resolution = loop {
+        let ending = lines.read(&mut reader)?;
+        crlf |= ending != b"\n";
+        let line = &lines.buf;
+
+        if line.is_empty() {
+            loop {
+                crlf |= lines.read(&mut reader)? != b"\n";
+                if !lines.buf.is_empty() {
+                    break;
+                }
+            }
+            break parse_resolution_lenient(&lines.buf, warnings)?;
+        }
 
+        if let Some(resolution) = parse_resolution_line(line) {
+            warnings.push(Warning::MissingBlankLine);
+            break resolution;
+        }
+
+        let text = String::from_utf8_lossy(line);
+        if let Some((name, value)) = text.split_once('=') {
+            let is_variable = header::is_variable(name);
+            let name = name.trim();
+            if is_variable && name == "EXPOSURE" && header::parse_positive(value).is_none() {
+                warnings.push(Warning::InvalidExposure(value.trim().to_owned()));
+            } else if is_variable && name == "GAMMA" && header::parse_positive(value).is_none() {
+                warnings.push(Warning::InvalidGamma(value.trim().to_owned()));
+            } else if is_variable && name == "PIXASPECT" && header::parse_positive(value).is_none()
+            {
+                warnings.push(Warning::InvalidPixelAspect(value.trim().to_owned()));
+            } else if is_variable
+                && name == "COLORCORR"
+                && header::parse_color_correction(value).is_none()
+            {
+                warnings.push(Warning::InvalidColorCorrection(value.trim().to_owned()));
+            } else if name == "FORMAT" {
+                if format_seen {
+                    warnings.push(Warning::DuplicateFormat);
+                }
+                format_seen = true;
+            } else if is_variable && !KNOWN_VARIABLES.contains(&name) {
+                warnings.push(Warning::UnknownVariable(name.to_owned()));
+            }
+        }
+
+        header.push_line(line);
+        header.raw_bytes.extend_from_slice(ending);
+    };
+    header.check_format()?;
+
+    if crlf {
+        warnings.push(Warning::NonStandardLineEnding);
+    }
+    Ok((header, resolution, reader))
+}
+
+/// Parse a resolution line without its EOL, or `None` if it is not valid.
+fn parse_resolution_line(line: &[u8]) -> Option<Resolution> {
+    parse_resolution(line.chain(&[EOL][..]))
+        .ok()
+        .map(|(resolution, _)| resolution)
+}
+
+/// Parse a resolution line without its EOL, failing with [`LoadError::InvalidDimensions`] if it
+/// is not valid.
+pub(crate) fn expect_resolution_line(line: &[u8]) -> LoadResult<Resolution> {
+    parse_resolution_line(line).ok_or_else(|| invalid_dimensions(line))
+}
+
+fn invalid_dimensions(line: &[u8]) -> LoadError {
+    LoadError::InvalidDimensions(String::from_utf8_lossy(line).into_owned())
+}
+
+/// Parse a resolution line without its EOL, cleaning it up first if it is not valid as is.
+fn parse_resolution_lenient(line: &[u8], warnings: &mut Vec<Warning>) -> LoadResult<Resolution> {
+    if let Some(resolution) = parse_resolution_line(line) {
+        return Ok(resolution);
+    }
+
+    // Uppercase the axes and put a single space between every sign, axis and size
+    let mut clean = Vec::with_capacity(line.len() * 2);
+    for &byte in line {
+        match byte {
+            b'+' | b'-' => {
+                clean.push(b' ');
+                clean.push(byte);
+            }
+            b'x' | b'X' | b'y' | b'Y' => clean.push(byte.to_ascii_uppercase()),
+            b'0'..=b'9' => {
+                if !clean.last().is_some_and(u8::is_ascii_digit) {
+                    clean.push(b' ');
+                }
+                clean.push(byte);
+            }
+            _ if byte.is_ascii_whitespace() => clean.push(b' '),
+            _ => return Err(invalid_dimensions(line)),
+        }
+    }
+
+    let resolution = parse_resolution_line(&clean).ok_or_else(|| invalid_dimensions(line))?;
+    warnings.push(Warning::SloppyResolutionLine);
+    Ok(resolution)
+}
+
+pub(crate) fn parse_resolution<R: BufRead>(reader: R) -> LoadResult<(Resolution, R)> {
     DimParser::new(reader)?.parse()
 }
 
@@ -26,11 +469,18 @@ impl<R: BufRead> DimParser<R> {
         Ok(Self { reader, byte })
     }
 
-    fn parse(mut self) -> LoadResult<(usize, usize, R)> {
+    fn parse(mut self) -> LoadResult<(Resolution, R)> {
         self.eat_whitespace()?;
-        let y = self.expect_y()?;
+        let (first, major) = self.expect_axis()?;
         self.expect_whitespace()?;
-        let x = self.expect_x()?;
+        let (second, minor) = self.expect_axis()?;
+
+        let orientation = Orientation::from_axes(first, second).ok_or(LoadError::FileFormat)?;
+        let (width, height) = if orientation.is_x_major() {
+            (major, minor)
+        } else {
+            (minor, major)
+        };
 
         while self.byte != EOL {
             if !self.byte.is_ascii_whitespace() {
@@ -40,7 +490,12 @@ impl<R: BufRead> DimParser<R> {
         }
 
         self.expect_eol()?;
-        Ok((x, y, self.reader))
+        let resolution = Resolution {
+            width,
+            height,
+            orientation,
+        };
+        Ok((resolution, self.reader))
     }
 
     fn eat_whitespace(&mut self) -> LoadResult {
@@ -70,27 +525,20 @@ impl<R: BufRead> DimParser<R> {
         Ok(self.byte)
     }
 
-    fn expect<B: AsRef<[u8]>>(&mut self, bytes: B) -> LoadResult {
-        for &byte in bytes.as_ref() {
-            if self.byte == byte {
-                self.eat()?;
-            } else {
-                return Err(LoadError::FileFormat);
-            }
-        }
-        Ok(())
-    }
-
-    fn expect_y(&mut self) -> LoadResult<usize> {
-        self.expect(b"-Y")?;
-        self.expect_whitespace()?;
-        self.expect_usize()
-    }
-
-    fn expect_x(&mut self) -> LoadResult<usize> {
-        self.expect(b"+X")?;
+    /// Parse a signed axis and its size, like `-Y 1024`. The sign is returned as `true` for `+`.
+    fn expect_axis(&mut self) -> LoadResult<((bool, u8), usize)> {
+        let positive = match self.byte {
+            b'+' => true,
+            b'-' => false,
+            _ => return Err(LoadError::FileFormat),
+        };
+        let axis = match self.eat()? {
+            axis @ b'X' | axis @ b'Y' => axis,
+            _ => return Err(LoadError::FileFormat),
+        };
+        self.eat()?;
         self.expect_whitespace()?;
-        self.expect_usize()
+        Ok(((positive, axis), self.expect_usize()?))
     }
 
     fn expect_usize(&mut self) -> LoadResult<usize> {
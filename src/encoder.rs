@@ -0,0 +1,147 @@
+use super::{header::is_variable, Header, Image, SaveError, SaveResult, MAGIC, NEW_RLE_LEN, RGBE};
+use std::io::Write;
+
+/// Save an [`Image`] in Radiance HDR format to a writer that implements [`Write`].
+///
+/// Scanlines are written flat, without run-length encoding.
+pub fn save<W: Write>(image: &Image, writer: W) -> SaveResult {
+    write_image(image, None, writer, false)
+}
+
+/// Save an [`Image`] in Radiance HDR format to a writer that implements [`Write`], keeping the
+/// lines of a [`Header`], e.g. one returned by [`load_with_header`](super::load_with_header).
+///
+/// Every line in [`raw`](Header::raw) is written except those setting `FORMAT` or `PIXASPECT`,
+/// which are taken from the image instead. Lines like `EXPOSURE` and `COLORCORR` are written as
+/// they are, so they should be removed from the header if the adjustments they describe have been
+/// undone.
+pub fn save_with_header<W: Write>(image: &Image, header: &Header, writer: W) -> SaveResult {
+    write_image(image, Some(header), writer, false)
+}
+
+/// Save an [`Image`] in Radiance HDR format to a writer that implements [`Write`], using
+/// run-length encoding.
+///
+/// Scanlines between 8 and 32767 pixels wide are written with the adaptive per-channel encoding
+/// that Radiance itself produces. Other scanlines are written flat, since the format cannot
+/// represent them.
+pub fn save_rle<W: Write>(image: &Image, writer: W) -> SaveResult {
+    write_image(image, None, writer, true)
+}
+
+/// Encode an [`Image`] in Radiance HDR format, returning the bytes.
+///
+/// The output is identical to what [`save`] writes.
+pub fn encode(image: &Image) -> SaveResult<Vec<u8>> {
+    let mut buf = Vec::with_capacity(image.data.len() * 4 + 64);
+    save(image, &mut buf)?;
+    Ok(buf)
+}
+
+fn write_image<W: Write>(
+    image: &Image,
+    header: Option<&Header>,
+    mut writer: W,
+    rle: bool,
+) -> SaveResult {
+    let length = image.width.checked_mul(image.height);
+    if length != Some(image.data.len()) {
+        return Err(SaveError::Dimensions);
+    }
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(b"\n")?;
+    for line in header.iter().flat_map(|header| &header.raw) {
+        let from_image = line.split_once('=').is_some_and(|(name, _)| {
+            is_variable(name) && (name.trim() == "FORMAT" || name.trim() == "PIXASPECT")
+        });
+        if !from_image {
+            writeln!(writer, "{}", line)?;
+        }
+    }
+    if image.pixel_aspect() != 1.0 {
+        writeln!(writer, "PIXASPECT={}", image.pixel_aspect())?;
+    }
+    writeln!(writer, "FORMAT={}\n", image.color_format.as_str())?;
+    writeln!(writer, "-Y {} +X {}", image.height, image.width)?;
+
+    let rle = rle && NEW_RLE_LEN.contains(&image.width);
+
+    if image.width > 0 {
+        let mut rgbe = Vec::with_capacity(image.width);
+        let mut buf = Vec::with_capacity(image.width * 4);
+        for scanline in image.data.chunks(image.width) {
+            rgbe.clear();
+            rgbe.extend(
+                scanline
+                    .iter()
+                    .map(|&pixel| <[u8; 4]>::from(RGBE::from(pixel))),
+            );
+
+            buf.clear();
+            if rle {
+                crunch(&mut buf, &rgbe);
+            } else {
+                rgbe.iter().for_each(|pixel| buf.extend_from_slice(pixel));
+            }
+            writer.write_all(&buf)?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn crunch(buf: &mut Vec<u8>, scanline: &[[u8; 4]]) {
+    let width = scanline.len();
+    buf.extend_from_slice(&[2, 2, (width >> 8) as u8, width as u8]);
+
+    let mut channel = Vec::with_capacity(width);
+    for i in 0..4 {
+        channel.clear();
+        channel.extend(scanline.iter().map(|pixel| pixel[i]));
+        crunch_channel(buf, &channel);
+    }
+}
+
+fn crunch_channel(buf: &mut Vec<u8>, channel: &[u8]) {
+    const MIN_RUN: usize = 4;
+    const MAX_RUN: usize = 127;
+    const MAX_LITERAL: usize = 128;
+
+    let run_length = |from: usize| {
+        channel[from..]
+            .iter()
+            .take(MAX_RUN)
+            .take_while(|&&val| val == channel[from])
+            .count()
+    };
+
+    let mut start = 0;
+    while start < channel.len() {
+        // Find the next run long enough to be worth encoding
+        let mut run_start = start;
+        let mut run_len = 0;
+        while run_start < channel.len() {
+            run_len = run_length(run_start);
+            if run_len >= MIN_RUN {
+                break;
+            }
+            run_start += run_len;
+            run_len = 0;
+        }
+
+        // Everything before the run is written as literals
+        for literal in channel[start..run_start].chunks(MAX_LITERAL) {
+            buf.push(literal.len() as u8);
+            buf.extend_from_slice(literal);
+        }
+
+        if run_len > 0 {
+            buf.push(128 | run_len as u8);
+            buf.push(channel[run_start]);
+        }
+
+        start = run_start + run_len;
+    }
+}
@@ -0,0 +1,77 @@
+use super::{decode_interleaved, read_preamble, Image, LoadResult, RGB};
+use half::f16;
+use std::io::BufRead;
+
+/// How [`load_f16`] and [`Image::to_f16`] convert pixels to half floats.
+///
+/// Each value is rounded to the nearest half float, with ties to even, as [`f16::from_f32`]
+/// does. Values too small for a normal half float become subnormal or zero.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct F16Options {
+    /// If set, a fourth channel with this value is added to every pixel.
+    pub alpha: Option<f16>,
+    /// Turn values above [`f16::MAX`] into infinity instead of clamping them to it. Bright light
+    /// sources easily exceed 65504.
+    pub allow_infinity: bool,
+}
+
+impl F16Options {
+    fn channel(&self, v: f32) -> f16 {
+        if self.allow_infinity {
+            f16::from_f32(v)
+        } else {
+            f16::from_f32(v.min(f16::MAX.to_f32()))
+        }
+    }
+
+    fn rgb(&self, pixel: &RGB) -> [f16; 3] {
+        [
+            self.channel(pixel.r),
+            self.channel(pixel.g),
+            self.channel(pixel.b),
+        ]
+    }
+
+    fn rgba(&self, pixel: &RGB, alpha: f16) -> [f16; 4] {
+        let [r, g, b] = self.rgb(pixel);
+        [r, g, b, alpha]
+    }
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`] as interleaved half
+/// floats, returning its width, height and three or four values per pixel.
+///
+/// Each scanline is converted as soon as it has been decoded, so the image is never held as
+/// 32-bit floats.
+pub fn load_f16<R: BufRead>(
+    reader: R,
+    options: F16Options,
+) -> LoadResult<(usize, usize, Vec<f16>)> {
+    let (_, resolution, reader) = read_preamble(reader)?;
+    let data = match options.alpha {
+        Some(alpha) => {
+            decode_interleaved(reader, resolution, |pixel: &RGB| options.rgba(pixel, alpha))?
+        }
+        None => decode_interleaved(reader, resolution, |pixel: &RGB| options.rgb(pixel))?,
+    };
+    Ok((resolution.width, resolution.height, data))
+}
+
+impl Image {
+    /// Convert to interleaved half floats, with three or four values per pixel in the same order
+    /// as [`data`](Self::data).
+    pub fn to_f16(&self, options: F16Options) -> Vec<f16> {
+        match options.alpha {
+            Some(alpha) => self
+                .data
+                .iter()
+                .flat_map(|pixel: &RGB| options.rgba(pixel, alpha))
+                .collect(),
+            None => self
+                .data
+                .iter()
+                .flat_map(|pixel: &RGB| options.rgb(pixel))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use super::{ColorMatrix, LoadError, LoadResult, Primaries, ViewParams};
+
+/// The way pixel values are encoded, as given by the `FORMAT` variable in the header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorFormat {
+    /// `32-bit_rle_rgbe`: red, green and blue with a shared exponent.
+    #[default]
+    Rgbe,
+    /// `32-bit_rle_xyze`: CIE X, Y and Z with a shared exponent.
+    Xyze,
+}
+
+impl ColorFormat {
+    /// The value of the `FORMAT` variable for this format.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Rgbe => "32-bit_rle_rgbe",
+            Self::Xyze => "32-bit_rle_xyze",
+        }
+    }
+}
+
+/// A line of a [`Header`], as returned by [`Header::lines`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HeaderLine<'a> {
+    /// A line that sets a variable.
+    Variable {
+        /// The name before the first `=`, without surrounding whitespace.
+        name: &'a str,
+        /// Everything after the first `=`, exactly as written.
+        value: &'a str,
+    },
+    /// Any other line, like a comment or the command that produced the image.
+    Comment(&'a str),
+}
+
+/// The information lines at the start of a Radiance HDR file, between the magic and the
+/// resolution line.
+///
+/// Variables that affect how pixel values should be interpreted are parsed into their own
+/// fields. Every line, including comments and variables that were parsed, is kept in `raw`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Header {
+    /// The program named after the `#?` on the first line of the file: usually `RADIANCE`, or
+    /// `RGBE` for files written by some other tools. Any other name is accepted too, except by
+    /// [`load_strict`](super::load_strict).
+    pub program: Option<String>,
+    /// The last `FORMAT` value, if it named a known format.
+    pub format: Option<ColorFormat>,
+    /// The product of all `EXPOSURE` values. Pixel values have been multiplied by this amount
+    /// since they were originally captured, which
+    /// [`apply_header_exposure`](super::Image::apply_header_exposure) undoes. Values that are
+    /// not positive numbers are ignored.
+    pub exposure: Option<f32>,
+    /// The last `GAMMA` value. Pixel values have been raised to the power of one over this
+    /// amount, which [`apply_gamma_decode`](super::Image::apply_gamma_decode) undoes. Values that
+    /// are not positive numbers are ignored.
+    pub gamma: Option<f32>,
+    /// The product of all `COLORCORR` values, per channel. Each channel has been multiplied by
+    /// its factor since the image was captured, which
+    /// [`undo_color_correction`](super::Image::undo_color_correction) undoes. Lines that are not
+    /// three positive numbers are ignored.
+    pub color_correction: Option<[f32; 3]>,
+    /// The last `PRIMARIES` value.
+    pub primaries: Option<Primaries>,
+    /// The last `SOFTWARE` value.
+    pub software: Option<String>,
+    /// The product of all `PIXASPECT` values: the height of a pixel divided by its width. Pixels
+    /// are square if there is none. Values that are not positive numbers are ignored.
+    pub pixel_aspect: Option<f32>,
+    /// The camera given by all `VIEW` values together.
+    pub view: Option<ViewParams>,
+    /// Variables that are not parsed into one of the fields above, like `CAPDATE` or ones written
+    /// by other tools, in order. Each value is exactly as written after the `=`.
+    pub other_variables: Vec<(String, String)>,
+    /// Lines that do not set a variable, like comments and the commands that produced the image,
+    /// in order.
+    pub comments: Vec<String>,
+    /// Every line of the header, in order, without line endings. Bytes that are not valid UTF-8
+    /// are replaced with `U+FFFD`.
+    pub raw: Vec<String>,
+    /// Every line of the header exactly as it was read, including line endings, as one block:
+    /// everything between the first line of the file and the empty line that ends the header.
+    pub raw_bytes: Vec<u8>,
+}
+
+impl Header {
+    /// The matrix that converts pixels of this image to CIE XYZ, derived from its `PRIMARIES`, or
+    /// from the default Radiance primaries if there are none.
+    pub fn rgb_to_xyz(&self) -> ColorMatrix {
+        self.primaries.unwrap_or_default().rgb_to_xyz()
+    }
+
+    /// Every line of the header, in order, telling variables apart from other lines.
+    pub fn lines(&self) -> impl Iterator<Item = HeaderLine<'_>> {
+        self.raw.iter().map(|line| match line.split_once('=') {
+            Some((name, value)) if is_variable(name) => HeaderLine::Variable {
+                name: name.trim(),
+                value,
+            },
+            _ => HeaderLine::Comment(line),
+        })
+    }
+
+    /// The lines of the header as one block of text, exactly as they were written between the
+    /// first line and the empty line that ends the header, like in
+    /// [`raw_bytes`](Self::raw_bytes).
+    ///
+    /// Line endings are kept as they were. Bytes that are not valid UTF-8 are replaced with
+    /// `U+FFFD`.
+    pub fn raw_text(&self) -> String {
+        String::from_utf8_lossy(&self.raw_bytes).into_owned()
+    }
+
+    /// The value of the last line that sets the variable `name`, without surrounding whitespace,
+    /// or `None` if no line does. This works for any variable, whether or not it is parsed into a
+    /// field.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.raw
+            .iter()
+            .rev()
+            .filter_map(|line| line.split_once('='))
+            .find(|(n, _)| is_variable(n) && n.trim() == name)
+            .map(|(_, value)| value.trim())
+    }
+
+    /// The width an image of `width` by `height` pixels should be displayed at, divided by its
+    /// height, taking the [`pixel_aspect`](Self::pixel_aspect) into account.
+    pub fn display_aspect_ratio(&self, width: usize, height: usize) -> f32 {
+        width as f32 / (height as f32 * self.pixel_aspect.unwrap_or(1.0))
+    }
+
+    /// Whether any `EXPOSURE`, `GAMMA` or `PIXASPECT` value is not a positive number, or any
+    /// `COLORCORR` value is not three positive numbers.
+    pub(crate) fn has_invalid_values(&self) -> bool {
+        let invalid = |name, valid: fn(&str) -> bool| self.values_of(name).any(|v| !valid(v));
+        invalid("EXPOSURE", |v| parse_positive(v).is_some())
+            || invalid("GAMMA", |v| parse_positive(v).is_some())
+            || invalid("PIXASPECT", |v| parse_positive(v).is_some())
+            || invalid("COLORCORR", |v| parse_color_correction(v).is_some())
+    }
+
+    /// Fail with [`LoadError::UnsupportedFormat`] if the last `FORMAT` value names a format that
+    /// cannot be decoded, or with [`LoadError::FileFormat`] if it is empty. Headers without a
+    /// `FORMAT` are taken to be RGBE, like many files in the wild expect.
+    pub(crate) fn check_format(&self) -> LoadResult {
+        match self.values_of("FORMAT").last().map(str::trim) {
+            Some("") => Err(LoadError::FileFormat),
+            Some(value) if self.format.is_none() => {
+                Err(LoadError::UnsupportedFormat(value.to_owned()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The values of every line that sets the variable `name`, in order.
+    fn values_of<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.raw
+            .iter()
+            .filter_map(|line| line.split_once('='))
+            .filter(move |(n, _)| is_variable(n) && n.trim() == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Record a line of the header and parse any variable it holds. Values that cannot be parsed
+    /// are ignored, like Radiance does. The line ending is left for the caller to add to
+    /// [`raw_bytes`](Self::raw_bytes).
+    pub(crate) fn push_line(&mut self, line: &[u8]) {
+        self.raw_bytes.extend_from_slice(line);
+        let line = String::from_utf8_lossy(line).into_owned();
+
+        match line.split_once('=') {
+            Some((name, value)) if is_variable(name) => self.push_variable(name.trim(), value),
+            _ => self.comments.push(line.clone()),
+        }
+
+        self.raw.push(line);
+    }
+
+    fn push_variable(&mut self, name: &str, verbatim: &str) {
+        let value = verbatim.trim();
+        match name {
+            "FORMAT" => {
+                self.format = [ColorFormat::Rgbe, ColorFormat::Xyze]
+                    .iter()
+                    .copied()
+                    .find(|format| format.as_str() == value);
+            }
+            "EXPOSURE" => {
+                if let Some(exposure) = parse_positive(value) {
+                    self.exposure = Some(self.exposure.unwrap_or(1.0) * exposure);
+                }
+            }
+            "GAMMA" => {
+                if let Some(gamma) = parse_positive(value) {
+                    self.gamma = Some(gamma);
+                }
+            }
+            "COLORCORR" => {
+                if let Some([r, g, b]) = parse_color_correction(value) {
+                    let [old_r, old_g, old_b] = self.color_correction.unwrap_or([1.0; 3]);
+                    self.color_correction = Some([old_r * r, old_g * g, old_b * b]);
+                }
+            }
+            "PRIMARIES" => {
+                if let Some([rx, ry, gx, gy, bx, by, wx, wy]) = parse_floats::<8>(value) {
+                    self.primaries = Some(Primaries {
+                        red: [rx, ry],
+                        green: [gx, gy],
+                        blue: [bx, by],
+                        white: [wx, wy],
+                    });
+                }
+            }
+            "SOFTWARE" => self.software = Some(value.to_owned()),
+            "PIXASPECT" => {
+                if let Some(aspect) = parse_positive(value) {
+                    self.pixel_aspect = Some(self.pixel_aspect.unwrap_or(1.0) * aspect);
+                }
+            }
+            "VIEW" => self
+                .view
+                .get_or_insert_with(ViewParams::default)
+                .apply(value),
+            _ => {
+                self.other_variables
+                    .push((name.to_owned(), verbatim.to_owned()));
+            }
+        }
+    }
+}
+
+/// Parse an `EXPOSURE`, `GAMMA` or `PIXASPECT` value, which must be a positive number.
+pub(crate) fn parse_positive(value: &str) -> Option<f32> {
+    value
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .filter(|value| value.is_finite() && *value > 0.0)
+}
+
+/// Parse a `COLORCORR` value, which must be three positive numbers.
+pub(crate) fn parse_color_correction(value: &str) -> Option<[f32; 3]> {
+    parse_floats::<3>(value).filter(|factors| factors.iter().all(|f| f.is_finite() && *f > 0.0))
+}
+
+/// Whether the part of a line before its first `=` names a variable, rather than being part of a
+/// comment or command line.
+pub(crate) fn is_variable(name: &str) -> bool {
+    let name = name.trim();
+    !name.is_empty() && !name.starts_with('#') && !name.contains(|c: char| c.is_ascii_whitespace())
+}
+
+/// Parse exactly `N` whitespace separated floats.
+fn parse_floats<const N: usize>(value: &str) -> Option<[f32; N]> {
+    let mut floats = [0.0; N];
+    let mut words = value.split_ascii_whitespace();
+    for float in &mut floats {
+        *float = words.next()?.parse().ok()?;
+    }
+    match words.next() {
+        Some(_) => None,
+        None => Some(floats),
+    }
+}
@@ -0,0 +1,31 @@
+use super::{Image, RGB};
+use image::Rgb32FImage;
+use std::convert::TryFrom;
+
+impl From<Image> for Rgb32FImage {
+    /// Convert into an image from the `image` crate, reusing the pixel buffer.
+    ///
+    /// [`Image::data`] is already in rows from top to bottom, each from left to right, which is
+    /// the layout the `image` crate expects, so the result is not flipped whatever the original
+    /// [`Orientation`](super::Orientation) was.
+    ///
+    /// Panics if the width or height does not fit in a `u32`, or if the length of the data does
+    /// not match them.
+    fn from(image: Image) -> Self {
+        let width = u32::try_from(image.width).expect("image is too wide");
+        let height = u32::try_from(image.height).expect("image is too tall");
+        Rgb32FImage::from_raw(width, height, into_floats(image.data))
+            .expect("image data does not match its dimensions")
+    }
+}
+
+/// Reinterpret pixels as three floats each, without copying them.
+fn into_floats(data: Vec<RGB>) -> Vec<f32> {
+    let mut data = std::mem::ManuallyDrop::new(data);
+    let (ptr, len, capacity) = (data.as_mut_ptr(), data.len(), data.capacity());
+
+    // SAFETY: RGB is repr(C) with three f32 fields and no padding, so it has the alignment of
+    // f32 and the size of three of them. The allocation is handed over to the new Vec, with its
+    // length and capacity counted in floats instead, and is never touched through `data` again.
+    unsafe { Vec::from_raw_parts(ptr.cast::<f32>(), len * 3, capacity * 3) }
+}
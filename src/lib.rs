@@ -1,4 +1,5 @@
 #![warn(missing_docs)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 
 //! # Radiant
 //!
@@ -39,14 +40,56 @@
 //! Huge thanks to [HDRI Haven](https://hdrihaven.com) for providing CC0 sample images for testing!
 
 // Original source: http://flipcode.com/archives/HDR_Image_Reader.shtml
-use std::io::{BufRead, Error as IoError, ErrorKind};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Error as IoError, ErrorKind, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 
+#[cfg(feature = "tokio")]
+mod asynchronous;
+mod color;
+mod context;
+mod decoder;
 mod dim_parser;
+mod encoder;
+#[cfg(feature = "half")]
+mod half_float;
+mod header;
+#[cfg(feature = "image")]
+mod image_crate;
+mod limits;
+mod orientation;
+mod push_decoder;
+mod resample;
+mod rgb9e5;
+#[cfg(feature = "simd")]
+mod simd;
+mod tone_map;
+mod view;
+mod warning;
+
+#[cfg(feature = "tokio")]
+pub use asynchronous::load_async;
+pub use color::{ColorMatrix, Primaries, WHITE_EFFICACY};
+pub use context::DecodeContext;
+pub use decoder::{for_each_scanline, load_pixels, Decoder};
+pub use encoder::{encode, save, save_rle, save_with_header};
+#[cfg(feature = "half")]
+pub use half_float::{load_f16, F16Options};
+pub use header::{ColorFormat, Header, HeaderLine};
+pub use limits::{Limit, LoadLimits};
+pub use orientation::Orientation;
+pub use push_decoder::{Progress, PushDecoder};
+pub use resample::{load_downscaled, load_region, load_thumbnail};
+pub use rgb9e5::{load_rgb9e5, rgb9e5_to_rgb, rgb_to_rgb9e5};
+pub use view::{ViewParams, ViewType};
+pub use warning::Warning;
 
 /// The decoded R, G, and B value of a pixel. You typically get these from the data field on an
 /// [`Image`].
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RGB {
     /// The red channel.
     pub r: f32,
@@ -57,10 +100,19 @@ pub struct RGB {
 }
 
 impl RGB {
+    /// A color with the same value in every channel.
+    pub fn splat(v: f32) -> Self {
+        Self { r: v, g: v, b: v }
+    }
+
     #[inline]
     fn apply_exposure(&mut self, expo: u8) {
-        let expo = i32::from(expo) - 128;
-        let d = 2_f32.powi(expo) / 255_f32;
+        // An exponent of 0 is black, like in Radiance itself
+        let d = if expo == 0 {
+            0.0
+        } else {
+            2_f32.powi(i32::from(expo) - 128) / 255_f32
+        };
 
         self.r *= d;
         self.g *= d;
@@ -68,12 +120,78 @@ impl RGB {
     }
 }
 
-#[derive(Debug, Clone)]
-struct RGBE {
-    r: u8,
-    g: u8,
-    b: u8,
-    e: u8,
+// SAFETY: RGB is repr(C) with three f32 fields, so it has no padding, and any bit pattern is a
+// valid value, including all zeros.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for RGB {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for RGB {}
+
+impl std::ops::Add for RGB {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            r: self.r + other.r,
+            g: self.g + other.g,
+            b: self.b + other.b,
+        }
+    }
+}
+
+impl std::ops::Sub for RGB {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            r: self.r - other.r,
+            g: self.g - other.g,
+            b: self.b - other.b,
+        }
+    }
+}
+
+impl std::ops::Mul<f32> for RGB {
+    type Output = Self;
+
+    fn mul(self, factor: f32) -> Self {
+        Self {
+            r: self.r * factor,
+            g: self.g * factor,
+            b: self.b * factor,
+        }
+    }
+}
+
+impl std::ops::Div<f32> for RGB {
+    type Output = Self;
+
+    fn div(self, divisor: f32) -> Self {
+        Self {
+            r: self.r / divisor,
+            g: self.g / divisor,
+            b: self.b / divisor,
+        }
+    }
+}
+
+/// A pixel as it is stored in a Radiance HDR file: three mantissas sharing one exponent. You
+/// typically get these from [`load_rgbe`], as arrays that convert to and from this type.
+///
+/// Each channel holds the value `mantissa / 255 * 2^(e - 128)`, so an exponent of 128 makes the
+/// mantissas a fraction of 1 and each step up or down doubles or halves them. An exponent of 0
+/// is black regardless of the mantissas. Converting to [`RGB`] gives the same value as [`load`].
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RGBE {
+    /// The red mantissa.
+    pub r: u8,
+    /// The green mantissa.
+    pub g: u8,
+    /// The blue mantissa.
+    pub b: u8,
+    /// The exponent shared by all three channels, offset by 128.
+    pub e: u8,
 }
 
 impl std::convert::From<RGBE> for RGB {
@@ -103,7 +221,69 @@ impl std::convert::From<RGBE> for [u8; 4] {
     }
 }
 
+impl std::convert::From<RGB> for RGBE {
+    #[inline]
+    fn from(rgb: RGB) -> Self {
+        let max = rgb.r.max(rgb.g).max(rgb.b);
+        if max.is_nan() || max < 1e-32 {
+            return Self {
+                r: 0,
+                g: 0,
+                b: 0,
+                e: 0,
+            };
+        }
+
+        // Find the smallest power of two that is at least max, so that max maps to a byte in
+        // (127, 255] given the 1/255 scale applied when decoding.
+        let bits = max.to_bits();
+        let mut exponent = ((bits >> 23) & 0xff) as i32 - 126;
+        if bits & 0x7f_ffff == 0 {
+            exponent -= 1;
+        }
+        if exponent > 127 {
+            return Self {
+                r: 255,
+                g: 255,
+                b: 255,
+                e: 255,
+            };
+        }
+
+        let d = 255_f32 / 2_f32.powi(exponent);
+        let quantize = |v: f32| (v * d + 0.5) as u8;
+        Self {
+            r: quantize(rgb.r),
+            g: quantize(rgb.g),
+            b: quantize(rgb.b),
+            e: (exponent + 128) as u8,
+        }
+    }
+}
+
 impl RGBE {
+    /// Encode a pixel, picking the exponent from its brightest channel. The mantissas are
+    /// rounded to the nearest value. Negative channels and pixels too dark to represent become
+    /// black, and pixels too bright to represent become the brightest value there is.
+    pub fn from_rgb(rgb: RGB) -> Self {
+        rgb.into()
+    }
+
+    /// Decode this pixel, the same way [`load`] does.
+    pub fn to_rgb(self) -> RGB {
+        self.into()
+    }
+
+    /// The pixel stored as the bytes `[r, g, b, e]`, in the order they appear in a file.
+    pub fn from_bytes(bytes: [u8; 4]) -> Self {
+        bytes.into()
+    }
+
+    /// The bytes `[r, g, b, e]` of this pixel, in the order they appear in a file.
+    pub fn to_bytes(self) -> [u8; 4] {
+        self.into()
+    }
+
     #[inline]
     fn is_rle_marker(&self) -> bool {
         self.r == 1 && self.g == 1 && self.b == 1
@@ -113,6 +293,42 @@ impl RGBE {
     fn is_new_decrunch_marker(&self) -> bool {
         self.r == 2 && self.g == 2 && self.b & 128 == 0
     }
+
+    /// Check that the length held by the blue and exponent bytes of a new-format marker is the
+    /// `len` of the scanline, failing with [`LoadError::Rle`] if it is not.
+    #[inline]
+    fn check_scanline_len(&self, len: usize) -> LoadResult {
+        if (usize::from(self.b) << 8 | usize::from(self.e)) == len {
+            Ok(())
+        } else {
+            Err(LoadError::Rle)
+        }
+    }
+
+    /// How many times an RLE marker repeats the previous pixel. Consecutive markers hold
+    /// successively higher bytes of the count, which `l_shift` says how far to shift by.
+    #[inline]
+    fn run_len(&self, l_shift: u32) -> LoadResult<usize> {
+        usize::checked_shl(1, l_shift)
+            .and_then(|shift_factor| usize::from(self.e).checked_mul(shift_factor))
+            .ok_or(LoadError::Rle)
+    }
+}
+
+/// Encode a pixel as the bytes `[r, g, b, e]` it is stored as in a Radiance HDR file, the same way
+/// [`RGBE::from_rgb`] does.
+///
+/// The exponent is that of the smallest power of two at least as bright as the brightest channel,
+/// so that channel gets a mantissa above 127, and 255 if it is exactly a power of two. Black
+/// pixels become `[0, 0, 0, 0]`, and negative channels become 0.
+pub fn rgb_to_rgbe(pixel: RGB) -> [u8; 4] {
+    RGBE::from(pixel).into()
+}
+
+/// Decode the bytes `[r, g, b, e]` of a pixel in a Radiance HDR file, the inverse of
+/// [`rgb_to_rgbe`].
+pub fn rgbe_to_rgb(bytes: [u8; 4]) -> RGB {
+    RGBE::from(bytes).into()
 }
 
 /// The various types of errors that can occur while loading an [`Image`].
@@ -127,9 +343,45 @@ pub enum LoadError {
     /// The file did not follow valid Radiance HDR format.
     #[error("invalid file format")]
     FileFormat,
+    /// The resolution line, given here without its line ending, could not be parsed.
+    #[error("invalid resolution line {0:?}")]
+    InvalidDimensions(String),
+    /// The header set `FORMAT` to a pixel format, given here, that cannot be decoded.
+    #[error("unsupported pixel format {0:?}")]
+    UnsupportedFormat(String),
     /// The image file contained invalid run-length encoding.
     #[error("invalid run-length encoding")]
     Rle,
+    /// The data was gzip-compressed. It can be loaded with `load_gz` or [`load_path`] when the
+    /// `gzip` feature is enabled.
+    #[error("data is gzip-compressed")]
+    Gzip,
+    /// The image or its header was larger than a [`LoadLimits`] allowed.
+    #[error("image exceeds the {0} limit")]
+    LimitExceeded(Limit),
+    /// Decoding was cancelled through the token given to [`load_cancellable`].
+    #[error("decoding was cancelled")]
+    Cancelled,
+    /// The region given to [`load_region`] did not fit inside the image.
+    #[error("region is outside the image")]
+    InvalidRegion,
+    /// A buffer given to a [`Decoder`], [`load_into_slice`] or [`Image::new`] was the wrong length.
+    #[error("buffer holds {actual} pixels, but {expected} are needed")]
+    BufferSize {
+        /// How many pixels were needed.
+        expected: usize,
+        /// How many pixels the buffer held.
+        actual: usize,
+    },
+    /// A [`Decoder`] was asked for more scanlines after the last one.
+    #[error("no scanlines left to decode")]
+    PastEnd,
+    /// There was more data after the image. Only returned by [`load_strict`].
+    #[error("{extra_bytes} bytes of trailing data after the image")]
+    TrailingData {
+        /// How many bytes followed the image.
+        extra_bytes: u64,
+    },
 }
 
 impl From<IoError> for LoadError {
@@ -144,6 +396,20 @@ impl From<IoError> for LoadError {
 /// An alias for the type of results this crate returns.
 pub type LoadResult<T = ()> = Result<T, LoadError>;
 
+/// The various types of errors that can occur while saving an [`Image`].
+#[derive(thiserror::Error, Debug)]
+pub enum SaveError {
+    /// A lower level io error was raised.
+    #[error("io error: {0}")]
+    Io(#[from] IoError),
+    /// The length of the image data did not match its width and height.
+    #[error("image data does not match its dimensions")]
+    Dimensions,
+}
+
+/// An alias for the type of results this crate returns when saving.
+pub type SaveResult<T = ()> = Result<T, SaveError>;
+
 trait ReadExt {
     fn read_byte(&mut self) -> std::io::Result<u8>;
     fn read_rgbe(&mut self) -> std::io::Result<RGBE>;
@@ -165,115 +431,491 @@ impl<R: BufRead> ReadExt for R {
     }
 }
 
-fn old_decrunch<R: BufRead>(mut reader: R, mut scanline: &mut [RGB]) -> LoadResult {
-    let mut l_shift = 0;
+/// A reader that keeps track of how many bytes have been consumed from it.
+#[derive(Debug)]
+struct CountingReader<R> {
+    inner: R,
+    count: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count += read as u64;
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for CountingReader<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.count += amt as u64;
+        self.inner.consume(amt);
+    }
+}
+
+/// A type that scanlines can be decoded into.
+pub(crate) trait DecodedPixel: Copy + Default {
+    /// How each channel of a scanline with the adaptive run-length encoding is written into a
+    /// pixel, in the order they are stored.
+    const CHANNELS: [fn(&mut Self, u8); 4];
+
+    /// Convert a whole stored pixel.
+    fn from_rgbe(rgbe: RGBE) -> Self;
+
+    /// Write the last channel of a whole scanline at once, instead of one pixel at a time with
+    /// the last of [`CHANNELS`](Self::CHANNELS).
+    #[cfg(feature = "simd")]
+    fn apply_exponents(scanline: &mut [Self], exponents: &[u8]);
+}
+
+impl DecodedPixel for RGB {
+    const CHANNELS: [fn(&mut Self, u8); 4] = [
+        |pixel, val| pixel.r = val as f32,
+        |pixel, val| pixel.g = val as f32,
+        |pixel, val| pixel.b = val as f32,
+        RGB::apply_exposure,
+    ];
+
+    #[inline]
+    fn from_rgbe(rgbe: RGBE) -> Self {
+        rgbe.into()
+    }
+
+    #[cfg(feature = "simd")]
+    fn apply_exponents(scanline: &mut [Self], exponents: &[u8]) {
+        simd::apply_exposure(scanline, exponents);
+    }
+}
+
+impl DecodedPixel for [u8; 4] {
+    const CHANNELS: [fn(&mut Self, u8); 4] = [
+        |pixel, val| pixel[0] = val,
+        |pixel, val| pixel[1] = val,
+        |pixel, val| pixel[2] = val,
+        |pixel, val| pixel[3] = val,
+    ];
+
+    #[inline]
+    fn from_rgbe(rgbe: RGBE) -> Self {
+        rgbe.into()
+    }
+
+    #[cfg(feature = "simd")]
+    fn apply_exponents(scanline: &mut [Self], exponents: &[u8]) {
+        for (pixel, &e) in scanline.iter_mut().zip(exponents) {
+            pixel[3] = e;
+        }
+    }
+}
+
+/// The state of the original run-length encoding from one scanline to the next.
+///
+/// Early writers treated the image as one long stream of pixels, so a run may repeat the last
+/// pixel of the previous scanline, or go on past the end of the scanline it starts in. Runs that
+/// go past the end of the image are still invalid.
+#[derive(Debug, Clone, Copy)]
+struct OldRun {
+    /// The last pixel that was not a run marker, unless the previous scanline was in the new
+    /// format or there was none.
+    last: Option<RGBE>,
+    /// How many more times `last` repeats at the start of the next scanline.
+    left: usize,
+    /// How far the count of the next run marker is shifted, if it follows another one.
+    l_shift: u32,
+    /// How many pixels of the image are left, from the start of the next scanline.
+    pixels_left: usize,
+}
+
+impl OldRun {
+    /// The state at the start of an image of `pixels` pixels.
+    fn new(pixels: usize) -> Self {
+        Self {
+            last: None,
+            left: 0,
+            l_shift: 0,
+            pixels_left: pixels,
+        }
+    }
+
+    /// Forget the previous scanline, because it was in the new format.
+    fn reset(&mut self) {
+        self.last = None;
+        self.left = 0;
+        self.l_shift = 0;
+    }
+
+    /// How many pixels at the start of the next scanline of `len` pixels are the rest of a run
+    /// from the previous one.
+    fn start_scanline(&mut self, len: usize) -> usize {
+        let count = self.left.min(len);
+        self.left -= count;
+        count
+    }
+
+    fn end_scanline(&mut self, len: usize) {
+        self.pixels_left = self.pixels_left.saturating_sub(len);
+    }
+
+    /// Account for the next pixel of an old-format scanline of `len` pixels, `x` of which are
+    /// done. Returns how many are done after it, and the pixel to repeat if it is a run marker.
+    #[inline]
+    fn step(&mut self, rgbe: RGBE, x: usize, len: usize) -> LoadResult<(usize, Option<RGBE>)> {
+        match self.last {
+            Some(last) if rgbe.is_rle_marker() => {
+                let count = rgbe.run_len(self.l_shift)?;
+                if count > self.pixels_left.saturating_sub(x) {
+                    return Err(LoadError::Rle);
+                }
+                let end = len.min(x + count);
+                self.left = x + count - end;
+                self.l_shift += 8;
+                Ok((end, Some(last)))
+            }
+            _ => {
+                self.last = Some(rgbe);
+                self.l_shift = 0;
+                Ok((x + 1, None))
+            }
+        }
+    }
+}
+
+/// Decode the next pixel of a scanline with flat pixels, or the original run-length encoding,
+/// into `scanline[x..]`. Returns how many pixels of the scanline are done after it.
+#[inline]
+fn old_pixel<P: DecodedPixel>(
+    rgbe: RGBE,
+    scanline: &mut [P],
+    x: usize,
+    run: &mut OldRun,
+) -> LoadResult<usize> {
+    let (end, repeat) = run.step(rgbe, x, scanline.len())?;
+    match repeat {
+        Some(from) => {
+            let from = P::from_rgbe(from);
+            scanline[x..end].iter_mut().for_each(|to| *to = from);
+        }
+        None => scanline[x] = P::from_rgbe(rgbe),
+    }
+    Ok(end)
+}
 
-    while scanline.len() > 1 {
+/// Decode the rest of a scanline with flat pixels, or the original run-length encoding, after
+/// the first `x` pixels.
+fn old_decrunch<R: BufRead, P: DecodedPixel>(
+    mut reader: R,
+    scanline: &mut [P],
+    mut x: usize,
+    run: &mut OldRun,
+) -> LoadResult {
+    while x < scanline.len() {
         let rgbe = reader.read_rgbe()?;
-        if rgbe.is_rle_marker() {
-            let count = usize::checked_shl(1, l_shift)
-                .and_then(|shift_factor| usize::from(rgbe.e).checked_mul(shift_factor))
-                .ok_or(LoadError::Rle)?;
+        x = old_pixel(rgbe, scanline, x, run)?;
+    }
+
+    Ok(())
+}
 
-            let from = scanline[0];
+/// The range of scanline widths that can use the new run-length encoding.
+const NEW_RLE_LEN: std::ops::RangeInclusive<usize> = 8..=0x7fff;
 
-            scanline
-                .get_mut(1..=count)
-                .ok_or(LoadError::Rle)?
-                .iter_mut()
-                .for_each(|to| *to = from);
+/// How a scanline is encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScanlineFormat {
+    /// Flat pixels, possibly with the original run-length encoding of whole pixels.
+    Old,
+    /// The adaptive run-length encoding of each channel separately.
+    New,
+}
+
+/// Decode one channel of a scanline with the adaptive run-length encoding.
+fn decrunch_channel<R: BufRead, T>(
+    mut reader: R,
+    mut scanline: &mut [T],
+    mutate_pixel: impl Fn(&mut T, u8),
+) -> LoadResult {
+    while !scanline.is_empty() {
+        let code = reader.read_byte()? as usize;
+        if code > 128 {
+            // run
+            let count = code & 127;
+            let pixels = scanline.get_mut(..count).ok_or(LoadError::Rle)?;
 
+            let val = reader.read_byte()?;
+            for pixel in pixels {
+                mutate_pixel(pixel, val);
+            }
             scanline = &mut scanline[count..];
-            l_shift += 8;
         } else {
-            scanline[1] = rgbe.into();
-            scanline = &mut scanline[1..];
-            l_shift = 0;
+            // non-run
+            let mut bytes_left = code;
+            while bytes_left > 0 {
+                let buf = reader.fill_buf()?;
+
+                if buf.is_empty() {
+                    #[cold]
+                    fn fail() -> LoadResult<()> {
+                        Err(LoadError::Eof(IoError::new(
+                            std::io::ErrorKind::UnexpectedEof,
+                            "failed to fill whole buffer",
+                        )))
+                    }
+
+                    return fail();
+                }
+
+                let count = buf.len().min(bytes_left);
+                let pixels = scanline.get_mut(..count).ok_or(LoadError::Rle)?;
+
+                for (pixel, &val) in pixels.iter_mut().zip(&buf[..count]) {
+                    mutate_pixel(pixel, val);
+                }
+                scanline = &mut scanline[count..];
+                reader.consume(count);
+                bytes_left -= count;
+            }
         }
     }
 
     Ok(())
 }
 
-fn decrunch<R: BufRead>(mut reader: R, scanline: &mut [RGB]) -> LoadResult {
-    const MIN_LEN: usize = 8;
-    const MAX_LEN: usize = 0x7fff;
+/// Decode a scanline, continuing the original run-length encoding from the previous one in
+/// `run`.
+fn decrunch<R: BufRead, P: DecodedPixel>(
+    mut reader: R,
+    scanline: &mut [P],
+    run: &mut OldRun,
+) -> LoadResult<ScanlineFormat> {
+    let format = decrunch_scanline(&mut reader, scanline, run)?;
+    run.end_scanline(scanline.len());
+    Ok(format)
+}
+
+fn decrunch_scanline<R: BufRead, P: DecodedPixel>(
+    mut reader: R,
+    scanline: &mut [P],
+    run: &mut OldRun,
+) -> LoadResult<ScanlineFormat> {
+    let carried = run.start_scanline(scanline.len());
+    if let Some(last) = run.last.filter(|_| carried > 0) {
+        let from = P::from_rgbe(last);
+        scanline[..carried].iter_mut().for_each(|to| *to = from);
+        old_decrunch(reader, scanline, carried, run)?;
+        return Ok(ScanlineFormat::Old);
+    }
+
+    let rgbe = reader.read_rgbe()?;
+
+    if !NEW_RLE_LEN.contains(&scanline.len()) || !rgbe.is_new_decrunch_marker() {
+        let x = old_pixel(rgbe, scanline, 0, run)?;
+        old_decrunch(reader, scanline, x, run)?;
+        return Ok(ScanlineFormat::Old);
+    }
+    rgbe.check_scanline_len(scanline.len())?;
+    run.reset();
+
+    // Call each channel separately, so that every call gets a known function to inline
+    #[cfg(not(feature = "simd"))]
+    {
+        let [r, g, b, e] = P::CHANNELS;
+        decrunch_channel(&mut reader, &mut *scanline, r)?;
+        decrunch_channel(&mut reader, &mut *scanline, g)?;
+        decrunch_channel(&mut reader, &mut *scanline, b)?;
+        decrunch_channel(&mut reader, &mut *scanline, e)?;
+    }
+
+    #[cfg(feature = "simd")]
+    {
+        for mutate_pixel in &P::CHANNELS[..3] {
+            decrunch_channel(&mut reader, scanline, mutate_pixel)?;
+        }
+        let mut exponents = vec![0; scanline.len()];
+        decrunch_channel(&mut reader, &mut exponents, |e, val| *e = val)?;
+        P::apply_exponents(scanline, &exponents);
+    }
+
+    Ok(ScanlineFormat::New)
+}
+
+/// Read past a scanline of `len` pixels without decoding it, continuing the original run-length
+/// encoding from the previous one in `run`. Invalid run-length encoding is still detected.
+fn skip_scanline<R: BufRead>(
+    mut reader: R,
+    len: usize,
+    run: &mut OldRun,
+) -> LoadResult<ScanlineFormat> {
+    let format = skip_scanline_data(&mut reader, len, run)?;
+    run.end_scanline(len);
+    Ok(format)
+}
+
+fn skip_scanline_data<R: BufRead>(
+    mut reader: R,
+    len: usize,
+    run: &mut OldRun,
+) -> LoadResult<ScanlineFormat> {
+    let mut x = run.start_scanline(len);
+    if x > 0 {
+        while x < len {
+            x = run.step(reader.read_rgbe()?, x, len)?.0;
+        }
+        return Ok(ScanlineFormat::Old);
+    }
 
     let rgbe = reader.read_rgbe()?;
 
-    if !(MIN_LEN..=MAX_LEN).contains(&scanline.len()) || !rgbe.is_new_decrunch_marker() {
-        scanline[0] = rgbe.into();
-        return old_decrunch(reader, scanline);
+    if !NEW_RLE_LEN.contains(&len) || !rgbe.is_new_decrunch_marker() {
+        let mut x = run.step(rgbe, 0, len)?.0;
+        while x < len {
+            x = run.step(reader.read_rgbe()?, x, len)?.0;
+        }
+        return Ok(ScanlineFormat::Old);
     }
+    rgbe.check_scanline_len(len)?;
+    run.reset();
 
-    let mut decrunch_channel = |mutate_pixel: fn(&mut RGB, u8)| {
-        let mut scanline = &mut scanline[..];
-        while !scanline.is_empty() {
+    for _ in 0..4 {
+        let mut left = len;
+        while left > 0 {
             let code = reader.read_byte()? as usize;
             if code > 128 {
                 // run
                 let count = code & 127;
-                let pixels = scanline.get_mut(..count).ok_or(LoadError::Rle)?;
-
-                let val = reader.read_byte()?;
-                for pixel in pixels {
-                    mutate_pixel(pixel, val);
-                }
-                scanline = &mut scanline[count..];
+                left = left.checked_sub(count).ok_or(LoadError::Rle)?;
+                reader.read_byte()?;
             } else {
                 // non-run
+                left = left.checked_sub(code).ok_or(LoadError::Rle)?;
                 let mut bytes_left = code;
                 while bytes_left > 0 {
-                    let buf = reader.fill_buf()?;
-
-                    if buf.is_empty() {
-                        #[cold]
-                        fn fail() -> LoadResult<()> {
-                            Err(LoadError::Eof(IoError::new(
-                                std::io::ErrorKind::UnexpectedEof,
-                                "failed to fill whole buffer",
-                            )))
-                        }
-
-                        return fail();
-                    }
-
-                    let count = buf.len().min(bytes_left);
-                    let pixels = scanline.get_mut(..count).ok_or(LoadError::Rle)?;
-
-                    for (pixel, &val) in pixels.iter_mut().zip(&buf[..count]) {
-                        mutate_pixel(pixel, val);
+                    let available = reader.fill_buf()?.len();
+                    if available == 0 {
+                        return Err(LoadError::Eof(IoError::new(
+                            ErrorKind::UnexpectedEof,
+                            "failed to fill whole buffer",
+                        )));
                     }
-                    scanline = &mut scanline[count..];
+                    let count = available.min(bytes_left);
                     reader.consume(count);
                     bytes_left -= count;
                 }
             }
         }
+    }
 
-        Ok(())
-    };
-
-    decrunch_channel(|pixel, val| pixel.r = val as f32)?;
-    decrunch_channel(|pixel, val| pixel.g = val as f32)?;
-    decrunch_channel(|pixel, val| pixel.b = val as f32)?;
-    decrunch_channel(RGB::apply_exposure)?;
-
-    Ok(())
+    Ok(ScanlineFormat::New)
 }
 
 /// A decoded Radiance HDR image.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Image {
     /// The width of the image, in pixels.
     pub width: usize,
     /// The height of the image, in pixels.
     pub height: usize,
-    /// The decoded image data.
+    /// The order the pixels were stored in. The data has already been rearranged into rows from
+    /// top to bottom, each from left to right, regardless of this value.
+    pub orientation: Orientation,
+    /// What the channels of each pixel hold. For [`ColorFormat::Xyze`] images, the `r`, `g` and
+    /// `b` fields of each pixel hold the X, Y and Z values.
+    pub color_format: ColorFormat,
+    pixel_aspect: f32,
+    /// The decoded image data, `width * height` pixels.
     pub data: Vec<RGB>,
 }
 
+/// A Radiance HDR image with the pixels as they were stored, returned by [`load_rgbe`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageRgbe {
+    /// The width of the image, in pixels.
+    pub width: usize,
+    /// The height of the image, in pixels.
+    pub height: usize,
+    /// The order the pixels were stored in. The data has already been rearranged into rows from
+    /// top to bottom, each from left to right, regardless of this value.
+    pub orientation: Orientation,
+    /// What the channels of each pixel hold.
+    pub color_format: ColorFormat,
+    /// The three mantissas and the shared exponent of each pixel, in that order, as an array
+    /// that converts to and from [`RGBE`].
+    pub data: Vec<[u8; 4]>,
+}
+
 impl Image {
+    /// Create an RGB image from pixel data in rows from top to bottom, each from left to right.
+    ///
+    /// Returns [`LoadError::BufferSize`] if `data` does not hold exactly `width * height` pixels,
+    /// or [`LoadError::LimitExceeded`] if that overflows.
+    pub fn new(width: usize, height: usize, data: Vec<RGB>) -> LoadResult<Image> {
+        let expected = width
+            .checked_mul(height)
+            .ok_or(LoadError::LimitExceeded(Limit::Pixels))?;
+        if data.len() != expected {
+            return Err(LoadError::BufferSize {
+                expected,
+                actual: data.len(),
+            });
+        }
+        Ok(Image {
+            width,
+            height,
+            orientation: Orientation::default(),
+            color_format: ColorFormat::Rgbe,
+            pixel_aspect: 1.0,
+            data,
+        })
+    }
+
+    /// Create an RGB image by calling `f` with the x and y coordinate of each pixel, in rows from
+    /// top to bottom, each from left to right.
+    pub fn from_fn<F: FnMut(usize, usize) -> RGB>(width: usize, height: usize, mut f: F) -> Image {
+        let data = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Image {
+            width,
+            height,
+            orientation: Orientation::default(),
+            color_format: ColorFormat::Rgbe,
+            pixel_aspect: 1.0,
+            data,
+        }
+    }
+
+    /// The height of a pixel divided by its width, from the [`pixel_aspect`](Header::pixel_aspect)
+    /// of the header, or 1.0 for square pixels. The pixels are never resampled for it, but
+    /// [`resample_to_square_pixels`](Self::resample_to_square_pixels) can do so.
+    pub fn pixel_aspect(&self) -> f32 {
+        self.pixel_aspect
+    }
+
+    /// Set the height of a pixel divided by its width, which [`save`] writes to the header.
+    pub fn set_pixel_aspect(&mut self, pixel_aspect: f32) {
+        self.pixel_aspect = pixel_aspect;
+    }
+
+    /// The number of pixels in the image. This is always `width * height`, the length of `data`.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether the image has no pixels, because its width or height is zero.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The width and height of the image, in pixels.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
     /// Calculate an offset into the data buffer, given an x and y coordinate.
     pub fn pixel_offset(&self, x: usize, y: usize) -> usize {
         self.width * y + x
@@ -284,46 +926,1249 @@ impl Image {
         let offset = self.pixel_offset(x, y);
         &self.data[offset]
     }
-}
 
-const MAGIC: &[u8; 10] = b"#?RADIANCE";
+    /// Get a mutable reference to a pixel at a specific x and y coordinate. Will panic if out of
+    /// bounds.
+    pub fn pixel_mut(&mut self, x: usize, y: usize) -> &mut RGB {
+        let offset = self.pixel_offset(x, y);
+        &mut self.data[offset]
+    }
 
-/// Load a Radiance HDR image from a reader that implements [`BufRead`].
-pub fn load<R: BufRead>(mut reader: R) -> LoadResult<Image> {
-    let mut buf = [0u8; MAGIC.len()];
-    reader.read_exact(&mut buf)?;
+    /// Get a pixel at a specific x and y coordinate, or `None` if it is out of bounds.
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<&RGB> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.data.get(self.pixel_offset(x, y))
+    }
 
-    if &buf != MAGIC {
-        return Err(LoadError::FileFormat);
+    /// Get a mutable reference to a pixel at a specific x and y coordinate, or `None` if it is
+    /// out of bounds.
+    pub fn get_pixel_mut(&mut self, x: usize, y: usize) -> Option<&mut RGB> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let offset = self.pixel_offset(x, y);
+        self.data.get_mut(offset)
     }
 
-    // Grab image dimensions
-    let (width, height, mut reader) = dim_parser::parse_header(reader)?;
+    /// View the pixel data as a flat slice of floats, three per pixel in R, G, B order, without
+    /// copying it.
+    #[cfg(feature = "bytemuck")]
+    pub fn as_f32_slice(&self) -> &[f32] {
+        bytemuck::cast_slice(&self.data)
+    }
 
-    let length = width.checked_mul(height).ok_or(LoadError::FileFormat)?;
+    /// Iterate over the rows of the image, from top to bottom. Each row holds `width` pixels.
+    pub fn rows(&self) -> std::slice::ChunksExact<'_, RGB> {
+        let len = if self.width == 0 { 0 } else { self.data.len() };
+        self.data[..len].chunks_exact(self.width.max(1))
+    }
+
+    /// Iterate over mutable rows of the image, from top to bottom. Each row holds `width` pixels.
+    pub fn rows_mut(&mut self) -> std::slice::ChunksExactMut<'_, RGB> {
+        let len = if self.width == 0 { 0 } else { self.data.len() };
+        self.data[..len].chunks_exact_mut(self.width.max(1))
+    }
+
+    /// Iterate over the pixels of the image along with their x and y coordinates, in the same
+    /// order as [`data`](Self::data).
+    pub fn enumerate_pixels(&self) -> impl Iterator<Item = (usize, usize, &RGB)> {
+        let width = self.width.max(1);
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(i, pixel)| (i % width, i / width, pixel))
+    }
+
+    /// The lowest and highest [luminance](RGB::luminance) of any pixel, or `None` if the image
+    /// has no pixels.
+    pub fn luminance_range(&self) -> Option<(f32, f32)> {
+        let mut luminances = self.data.iter().map(RGB::luminance);
+        let first = luminances.next()?;
+        Some(luminances.fold((first, first), |(min, max), l| (min.min(l), max.max(l))))
+    }
+
+    /// A new image of the same size with `f` applied to every pixel, in the order of
+    /// [`data`](Self::data).
+    pub fn map<F: FnMut(RGB) -> RGB>(&self, mut f: F) -> Image {
+        Image {
+            width: self.width,
+            height: self.height,
+            orientation: self.orientation,
+            color_format: self.color_format,
+            pixel_aspect: self.pixel_aspect,
+            data: self.data.iter().map(|&pixel| f(pixel)).collect(),
+        }
+    }
+
+    /// Replace every pixel with the result of applying `f` to it, in the order of
+    /// [`data`](Self::data).
+    pub fn map_in_place<F: FnMut(RGB) -> RGB>(&mut self, mut f: F) {
+        for pixel in &mut self.data {
+            *pixel = f(*pixel);
+        }
+    }
+
+    /// Whether `other` has the same width and height and every channel of every pixel is within
+    /// `epsilon` of the same channel in this image. NaN channels are never equal to anything.
+    pub fn approx_eq(&self, other: &Image, epsilon: f32) -> bool {
+        self.approx_eq_by(other, |a, b| a == b || (a - b).abs() <= epsilon)
+    }
+
+    /// Like [`approx_eq`](Self::approx_eq), but every channel may differ by at most
+    /// `max_relative` times the larger of the two values, which suits images with both very dark
+    /// and very bright pixels.
+    pub fn approx_eq_relative(&self, other: &Image, max_relative: f32) -> bool {
+        self.approx_eq_by(other, |a, b| {
+            a == b || (a - b).abs() <= max_relative * a.abs().max(b.abs())
+        })
+    }
+
+    fn approx_eq_by<F: Fn(f32, f32) -> bool>(&self, other: &Image, eq: F) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.data.len() == other.data.len()
+            && self
+                .data
+                .iter()
+                .zip(&other.data)
+                .all(|(a, b)| eq(a.r, b.r) && eq(a.g, b.g) && eq(a.b, b.b))
+    }
+
+    /// Clamp every channel of every pixel to the range from `min` to `max`. NaN channels become
+    /// `min`.
+    ///
+    /// Panics if `min` is greater than `max`, or if either is NaN.
+    pub fn clamp(&mut self, min: f32, max: f32) {
+        assert!(min <= max, "invalid range {}..={}", min, max);
+        let clamp = |v: f32| if v.is_nan() { min } else { v.clamp(min, max) };
+        for pixel in &mut self.data {
+            pixel.r = clamp(pixel.r);
+            pixel.g = clamp(pixel.g);
+            pixel.b = clamp(pixel.b);
+        }
+    }
+
+    /// Divide every channel of every pixel by the brightest finite channel in the image, so that
+    /// it becomes 1. NaN and infinite channels are ignored when looking for the brightest one.
+    /// Does nothing if no channel is finite and greater than 0, like for an all black image.
+    pub fn normalize(&mut self) {
+        let max = self
+            .data
+            .iter()
+            .flat_map(|pixel| [pixel.r, pixel.g, pixel.b])
+            .filter(|v| v.is_finite())
+            .fold(0.0, f32::max);
+        if max > 0.0 {
+            for pixel in &mut self.data {
+                *pixel = *pixel / max;
+            }
+        }
+    }
+
+    /// Divide every pixel by the [`exposure`](Header::exposure) of `header`, undoing any exposure
+    /// adjustments made after the image was captured so that pixel values are in absolute
+    /// radiance again. Does nothing if the header has no exposure.
+    pub fn apply_header_exposure(&mut self, header: &Header) {
+        if let Some(exposure) = header.exposure {
+            for pixel in &mut self.data {
+                *pixel = *pixel / exposure;
+            }
+        }
+    }
 
-    // Allocate result buffer
-    let mut data = vec![
-        RGB {
-            r: 0.0,
-            g: 0.0,
-            b: 0.0,
+    /// Convert every pixel to the color space with the `target` primaries, e.g.
+    /// [`Primaries::REC709`], adapting the white point if it differs.
+    ///
+    /// The pixels are assumed to use the [`primaries`](Header::primaries) of `header`, or the
+    /// default Radiance primaries if it has none. [`ColorFormat::Xyze`] images are converted from
+    /// CIE XYZ instead, and become [`ColorFormat::Rgbe`] images.
+    pub fn convert_primaries(&mut self, header: &Header, target: &Primaries) {
+        let matrix = match self.color_format {
+            ColorFormat::Rgbe => header.primaries.unwrap_or_default().conversion_to(target),
+            ColorFormat::Xyze => color::xyz_conversion_to(target),
         };
-        length
-    ];
+        for pixel in &mut self.data {
+            let [r, g, b] = pixel.transform(&matrix);
+            *pixel = RGB { r, g, b };
+        }
+        self.color_format = ColorFormat::Rgbe;
+    }
 
-    if length > 0 {
-        // Decrunch image data
-        for row in 0..height {
-            let start = row * width;
-            let end = start + width;
-            decrunch(&mut reader, &mut data[start..end])?;
+    /// Raise every channel of every pixel to the power of `gamma`, turning pixel values that were
+    /// gamma encoded, as indicated by the [`gamma`](Header::gamma) of the header, back into linear
+    /// values. Does nothing if `gamma` is 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `gamma` is not a positive number.
+    pub fn apply_gamma_decode(&mut self, gamma: f32) {
+        assert!(
+            gamma.is_finite() && gamma > 0.0,
+            "gamma must be a positive number, got {}",
+            gamma
+        );
+        if gamma == 1.0 {
+            return;
+        }
+        for pixel in &mut self.data {
+            pixel.r = pixel.r.powf(gamma);
+            pixel.g = pixel.g.powf(gamma);
+            pixel.b = pixel.b.powf(gamma);
         }
     }
 
-    Ok(Image {
-        width,
-        height,
-        data,
-    })
+    /// Divide each channel of every pixel by its factor in the
+    /// [`color_correction`](Header::color_correction) of `header`, undoing the color balancing
+    /// done by tools like `pcond` and `pfilt`. Does nothing if the header has no color
+    /// correction.
+    pub fn undo_color_correction(&mut self, header: &Header) {
+        if let Some([r, g, b]) = header.color_correction {
+            for pixel in &mut self.data {
+                pixel.r /= r;
+                pixel.g /= g;
+                pixel.b /= b;
+            }
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Image {
+    type Item = &'a RGB;
+    type IntoIter = std::slice::Iter<'a, RGB>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Image {
+    type Item = &'a mut RGB;
+    type IntoIter = std::slice::IterMut<'a, RGB>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter_mut()
+    }
+}
+
+impl std::ops::Index<(usize, usize)> for Image {
+    type Output = RGB;
+
+    /// Get the pixel at an x and y coordinate, like [`Image::pixel`]. Will panic if out of
+    /// bounds.
+    fn index(&self, (x, y): (usize, usize)) -> &RGB {
+        self.pixel(x, y)
+    }
+}
+
+impl std::ops::IndexMut<(usize, usize)> for Image {
+    /// Get a mutable reference to the pixel at an x and y coordinate, like [`Image::pixel_mut`].
+    /// Will panic if out of bounds.
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut RGB {
+        self.pixel_mut(x, y)
+    }
+}
+
+const MAGIC: &[u8; 10] = b"#?RADIANCE";
+/// The start of the first line of every file, which goes on to name the program that wrote it.
+const MAGIC_PREFIX: &[u8; 2] = b"#?";
+const GZIP_MAGIC: &[u8; 2] = b"\x1f\x8b";
+
+fn read_magic<R: BufRead>(reader: &mut R) -> LoadResult {
+    let mut buf = [0u8; MAGIC_PREFIX.len()];
+    reader.read_exact(&mut buf)?;
+    check_magic(&buf)
+}
+
+/// Check the first bytes of a file, telling gzip-compressed data apart from anything else that
+/// is not an image.
+fn check_magic(buf: &[u8]) -> LoadResult {
+    if buf != MAGIC_PREFIX {
+        if buf == GZIP_MAGIC {
+            return Err(LoadError::Gzip);
+        }
+        return Err(LoadError::FileFormat);
+    }
+    Ok(())
+}
+
+/// Check the magic and parse the header, leaving the reader at the start of the pixel data.
+fn read_preamble<R: BufRead>(reader: R) -> LoadResult<(Header, dim_parser::Resolution, R)> {
+    read_preamble_with(reader, &LoadLimits::header_only(), true)
+}
+
+/// Like [`read_preamble`], but fail if the header is larger than `limits` allow. Unless `capture`
+/// is set, only the [`format`](Header::format) and [`pixel_aspect`](Header::pixel_aspect) of the
+/// header are filled in, which avoids allocating.
+fn read_preamble_with<R: BufRead>(
+    mut reader: R,
+    limits: &LoadLimits,
+    capture: bool,
+) -> LoadResult<(Header, dim_parser::Resolution, R)> {
+    read_magic(&mut reader)?;
+    dim_parser::parse_header(reader, limits, capture)
+}
+
+/// Everything that can be learned about a Radiance HDR image without decoding its pixels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageInfo {
+    /// The width of the image, in pixels.
+    pub width: usize,
+    /// The height of the image, in pixels.
+    pub height: usize,
+    /// The order the pixels are stored in.
+    pub orientation: Orientation,
+    /// The information in the header.
+    pub header: Header,
+}
+
+/// Read the header and resolution of a Radiance HDR image from a reader that implements
+/// [`BufRead`], stopping before the pixel data.
+///
+/// Nothing past the resolution line is read, so this works on truncated files as long as the
+/// header is intact.
+pub fn read_header<R: BufRead>(reader: R) -> LoadResult<ImageInfo> {
+    let (header, resolution, _) = read_preamble(reader)?;
+    Ok(ImageInfo {
+        width: resolution.width,
+        height: resolution.height,
+        orientation: resolution.orientation,
+        header,
+    })
+}
+
+/// Read the width and height of a Radiance HDR image from a reader that implements [`BufRead`],
+/// without decoding any pixel data.
+pub fn load_dimensions<R: BufRead>(reader: R) -> LoadResult<(usize, usize)> {
+    let info = read_header(reader)?;
+    Ok((info.width, info.height))
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`].
+///
+/// The size of the image is not limited in any way, so a malicious file can make this allocate
+/// an arbitrary amount of memory. Use [`load_with_limits`] for untrusted input. The header is
+/// held to the [default](LoadLimits::default) limits, and fails with
+/// [`LoadError::LimitExceeded`] if it is larger.
+///
+/// Only the bytes that belong to the image are consumed, so the reader can be used to read
+/// whatever follows it afterwards.
+///
+/// With the `rayon` feature, the compressed scanlines are read into memory first and then
+/// decoded in parallel.
+pub fn load<R: BufRead>(reader: R) -> LoadResult<Image> {
+    decode(reader, &LoadLimits::header_only(), false).map(|(image, _)| image)
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`], failing with
+/// [`LoadError::TrailingData`] if anything follows it.
+///
+/// A single trailing newline is allowed, since some tools write one. The reader is read to the
+/// end in order to count the trailing bytes. An `EXPOSURE`, `GAMMA` or `PIXASPECT` value that is
+/// not a positive number, or a `COLORCORR` value that is not three positive numbers, fails with
+/// [`LoadError::FileFormat`] instead of being ignored like [`load`] does. So does a first line
+/// that names a program other than `RADIANCE` or `RGBE` after the `#?`.
+pub fn load_strict<R: BufRead>(mut reader: R) -> LoadResult<Image> {
+    let (image, header) = load_with_header(&mut reader)?;
+    let known_program = header
+        .program
+        .as_deref()
+        .is_some_and(dim_parser::is_known_program);
+    if !known_program || header.has_invalid_values() {
+        return Err(LoadError::FileFormat);
+    }
+
+    let mut extra_bytes = 0u64;
+    let mut trailing_newline = true;
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+        trailing_newline = extra_bytes == 0 && buf == [dim_parser::EOL];
+        extra_bytes += buf.len() as u64;
+        let len = buf.len();
+        reader.consume(len);
+    }
+
+    if extra_bytes == 0 || (extra_bytes == 1 && trailing_newline) {
+        Ok(image)
+    } else {
+        Err(LoadError::TrailingData { extra_bytes })
+    }
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`], recovering from quirks in
+/// the header that [`load`] rejects.
+///
+/// Every quirk that was forgiven is returned as a [`Warning`], in the order they were found.
+/// Errors in the pixel data are not recovered from.
+pub fn load_lenient<R: BufRead>(mut reader: R) -> LoadResult<(Image, Vec<Warning>)> {
+    read_magic(&mut reader)?;
+    let mut warnings = Vec::new();
+    let (header, resolution, reader) =
+        dim_parser::parse_header_lenient(reader, &LoadLimits::header_only(), &mut warnings)?;
+    let (image, _) = decode_pixels(reader, header, resolution, &LoadLimits::none(), |_, _| {
+        Ok(())
+    })?;
+    Ok((image, warnings))
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`], along with the
+/// information in its header.
+pub fn load_with_header<R: BufRead>(reader: R) -> LoadResult<(Image, Header)> {
+    decode(reader, &LoadLimits::header_only(), true)
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`], converting its pixels to
+/// linear RGB with the Rec. 709 primaries that sRGB uses.
+///
+/// [`ColorFormat::Rgbe`] images are converted from the [`primaries`](Header::primaries) in their
+/// header, or the default Radiance ones, and [`ColorFormat::Xyze`] images from CIE XYZ, as
+/// [`Image::convert_primaries`] does. Use [`load`] to get the pixels as they are stored instead,
+/// with [`color_format`](Image::color_format) saying which of the two they are.
+pub fn load_rec709<R: BufRead>(reader: R) -> LoadResult<Image> {
+    let (mut image, header) = load_with_header(reader)?;
+    image.convert_primaries(&header, &Primaries::REC709);
+    Ok(image)
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`], failing with
+/// [`LoadError::LimitExceeded`] if it or its header is larger than `limits` allow.
+///
+/// [`load`] does not limit the size of images, so this should be used instead for untrusted
+/// input.
+pub fn load_with_limits<R: BufRead>(reader: R, limits: &LoadLimits) -> LoadResult<Image> {
+    decode(reader, limits, false).map(|(image, _)| image)
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`] and [`Seek`].
+///
+/// Before allocating memory for the pixel data, the length of the stream is used to reject
+/// images that claim more scanlines than the remaining bytes could possibly hold. In that case
+/// [`LoadError::FileFormat`] is returned and the reader is moved back to where the image
+/// started. If the reader cannot actually seek, like a pipe, this behaves like [`load`].
+pub fn load_seek<R: BufRead + Seek>(mut reader: R) -> LoadResult<Image> {
+    let start = reader.stream_position().ok();
+    let (header, resolution, mut reader) = read_preamble(reader)?;
+
+    if let (Some(start), Some(remaining)) = (start, remaining_len(&mut reader)?) {
+        let scanlines = resolution.scanlines();
+
+        // Every scanline starts with at least one whole pixel
+        if (scanlines as u64).saturating_mul(4) > remaining {
+            reader.seek(SeekFrom::Start(start))?;
+            return Err(LoadError::FileFormat);
+        }
+    }
+
+    decode_pixels(reader, header, resolution, &LoadLimits::none(), |_, _| {
+        Ok(())
+    })
+    .map(|(image, _)| image)
+}
+
+/// How many bytes are left in a stream, or `None` if it cannot seek.
+fn remaining_len<S: Seek>(stream: &mut S) -> LoadResult<Option<u64>> {
+    let position = match stream.stream_position() {
+        Ok(position) => position,
+        Err(_) => return Ok(None),
+    };
+    let end = match stream.seek(SeekFrom::End(0)) {
+        Ok(end) => end,
+        Err(_) => return Ok(None),
+    };
+    stream.seek(SeekFrom::Start(position))?;
+    Ok(Some(end.saturating_sub(position)))
+}
+
+fn decode<R: BufRead>(
+    reader: R,
+    limits: &LoadLimits,
+    capture: bool,
+) -> LoadResult<(Image, Header)> {
+    // Grab image dimensions
+    let (header, resolution, reader) = read_preamble_with(reader, limits, capture)?;
+
+    #[cfg(feature = "rayon")]
+    return {
+        let data = decode_data_parallel(reader, resolution, limits)?;
+        let image = Image {
+            width: resolution.width,
+            height: resolution.height,
+            orientation: resolution.orientation,
+            color_format: header.format.unwrap_or_default(),
+            pixel_aspect: header.pixel_aspect.unwrap_or(1.0),
+            data,
+        };
+        Ok((image, header))
+    };
+
+    #[cfg(not(feature = "rayon"))]
+    decode_pixels(reader, header, resolution, limits, |_, _| Ok(()))
+}
+
+/// A reader that keeps a copy of every byte consumed from it.
+#[cfg(feature = "rayon")]
+struct RecordingReader<'a, R> {
+    inner: R,
+    record: &'a mut Vec<u8>,
+}
+
+#[cfg(feature = "rayon")]
+impl<R: BufRead> Read for RecordingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.record.extend_from_slice(&buf[..read]);
+        Ok(read)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<R: BufRead> BufRead for RecordingReader<'_, R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The buffer is still filled from the call that preceded this one
+        if let Ok(buf) = self.inner.fill_buf() {
+            self.record.extend_from_slice(&buf[..amt]);
+        }
+        self.inner.consume(amt);
+    }
+}
+
+/// Like [`decode_data`], but decode the scanlines in parallel.
+///
+/// The scanlines are first read into memory one after another, without decoding them, to find
+/// where each one starts. Only the bytes of the image are consumed from the reader.
+#[cfg(feature = "rayon")]
+fn decode_data_parallel<R: BufRead, P: DecodedPixel + Send>(
+    mut reader: R,
+    resolution: dim_parser::Resolution,
+    limits: &LoadLimits,
+) -> LoadResult<Vec<P>> {
+    use rayon::prelude::*;
+
+    let dim_parser::Resolution {
+        width,
+        height,
+        orientation,
+    } = resolution;
+
+    let length = width.checked_mul(height).ok_or(LoadError::FileFormat)?;
+    limits.check(width, height)?;
+    if length == 0 {
+        return Ok(Vec::new());
+    }
+
+    let scanline_len = orientation.scanline_len(width, height);
+    // Find where each scanline starts and ends, and the state of the original run-length
+    // encoding at each start
+    let mut bytes = Vec::new();
+    let mut bounds = vec![0];
+    let mut runs = Vec::with_capacity(length / scanline_len);
+    let mut run = OldRun::new(length);
+    let mut recording = RecordingReader {
+        inner: &mut reader,
+        record: &mut bytes,
+    };
+    for _ in 0..length / scanline_len {
+        runs.push(run);
+        skip_scanline(&mut recording, scanline_len, &mut run)?;
+        bounds.push(recording.record.len());
+    }
+
+    let mut data = vec![P::default(); length];
+    data.par_chunks_mut(scanline_len)
+        .zip(bounds.par_windows(2))
+        .zip(runs)
+        .try_for_each(|((scanline, bounds), mut run)| {
+            decrunch(&bytes[bounds[0]..bounds[1]], scanline, &mut run).map(|_| ())
+        })?;
+    orientation.normalize(&mut data, width, height);
+
+    Ok(data)
+}
+
+/// Decode the pixel data, calling `scanline_done` with the number of scanlines decoded so far
+/// after each one. Decoding stops if it returns an error.
+fn decode_pixels<R: BufRead>(
+    reader: R,
+    header: Header,
+    resolution: dim_parser::Resolution,
+    limits: &LoadLimits,
+    scanline_done: impl FnMut(usize, &R) -> LoadResult,
+) -> LoadResult<(Image, Header)> {
+    let data = decode_data(reader, resolution, limits, scanline_done)?;
+    let image = Image {
+        width: resolution.width,
+        height: resolution.height,
+        orientation: resolution.orientation,
+        color_format: header.format.unwrap_or_default(),
+        pixel_aspect: header.pixel_aspect.unwrap_or(1.0),
+        data,
+    };
+    Ok((image, header))
+}
+
+/// Decode the pixel data into pixels of any type, rearranged into rows from top to bottom.
+fn decode_data<R: BufRead, P: DecodedPixel>(
+    reader: R,
+    resolution: dim_parser::Resolution,
+    limits: &LoadLimits,
+    scanline_done: impl FnMut(usize, &R) -> LoadResult,
+) -> LoadResult<Vec<P>> {
+    let mut data = Vec::new();
+    decode_into(
+        reader,
+        resolution,
+        limits,
+        &mut data,
+        &mut Vec::new(),
+        scanline_done,
+    )?;
+    Ok(data)
+}
+
+/// Like [`decode_data`], but reuse the allocation of `data`, and of `scanline` for decoding each
+/// scanline into.
+fn decode_into<R: BufRead, P: DecodedPixel>(
+    mut reader: R,
+    resolution: dim_parser::Resolution,
+    limits: &LoadLimits,
+    data: &mut Vec<P>,
+    scanline: &mut Vec<P>,
+    mut scanline_done: impl FnMut(usize, &R) -> LoadResult,
+) -> LoadResult {
+    let dim_parser::Resolution {
+        width,
+        height,
+        orientation,
+    } = resolution;
+
+    let length = width.checked_mul(height).ok_or(LoadError::FileFormat)?;
+    limits.check(width, height)?;
+
+    // Allocate result buffer, without initializing it
+    data.clear();
+    data.reserve(length);
+
+    if length > 0 {
+        // Decrunch image data into a small buffer that stays in cache, so that every pixel of
+        // the result is only written once
+        let scanline_len = orientation.scanline_len(width, height);
+        scanline.clear();
+        scanline.resize(scanline_len, P::default());
+        let mut run = OldRun::new(length);
+        for i in 0..length / scanline_len {
+            decrunch(&mut reader, &mut scanline[..], &mut run)?;
+            data.extend_from_slice(scanline);
+            scanline_done(i + 1, &reader)?;
+        }
+        orientation.normalize(data, width, height);
+    }
+
+    Ok(())
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`] into an existing buffer,
+/// returning its width and height.
+///
+/// The buffer is resized to fit the image, so its allocation can be reused when loading many
+/// images of the same size. Its contents are unspecified if an error is returned.
+pub fn load_into<R: BufRead>(reader: R, data: &mut Vec<RGB>) -> LoadResult<(usize, usize)> {
+    let (_, resolution, reader) = read_preamble_with(reader, &LoadLimits::header_only(), false)?;
+    decode_into(
+        reader,
+        resolution,
+        &LoadLimits::none(),
+        data,
+        &mut Vec::new(),
+        |_, _| Ok(()),
+    )?;
+    Ok((resolution.width, resolution.height))
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`] into the start of a
+/// caller-provided buffer, returning its width and height.
+///
+/// Fails with [`LoadError::BufferSize`] if `data` holds fewer than `width * height` pixels.
+/// Pixels past those are left untouched, and the contents of the rest are unspecified if an
+/// error is returned.
+pub fn load_into_slice<R: BufRead>(reader: R, data: &mut [RGB]) -> LoadResult<(usize, usize)> {
+    let (_, resolution, mut reader) = read_preamble(reader)?;
+    let dim_parser::Resolution {
+        width,
+        height,
+        orientation,
+    } = resolution;
+
+    let length = width.checked_mul(height).ok_or(LoadError::FileFormat)?;
+    if data.len() < length {
+        return Err(LoadError::BufferSize {
+            expected: length,
+            actual: data.len(),
+        });
+    }
+
+    if length > 0 {
+        let data = &mut data[..length];
+        let mut run = OldRun::new(length);
+        for scanline in data.chunks_exact_mut(orientation.scanline_len(width, height)) {
+            decrunch(&mut reader, scanline, &mut run)?;
+        }
+        orientation.normalize(data, width, height);
+    }
+
+    Ok((width, height))
+}
+
+/// A pixel type that [`load_with`] can decode into.
+pub trait FromRgbe: Copy {
+    /// Convert a pixel as it is stored in the file: three mantissas sharing one exponent.
+    fn from_rgbe(r: u8, g: u8, b: u8, e: u8) -> Self;
+}
+
+impl FromRgbe for RGB {
+    #[inline]
+    fn from_rgbe(r: u8, g: u8, b: u8, e: u8) -> Self {
+        RGBE { r, g, b, e }.into()
+    }
+}
+
+impl FromRgbe for [f32; 3] {
+    #[inline]
+    fn from_rgbe(r: u8, g: u8, b: u8, e: u8) -> Self {
+        let RGB { r, g, b } = RGBE { r, g, b, e }.into();
+        [r, g, b]
+    }
+}
+
+/// Alpha is always 1.
+impl FromRgbe for [f32; 4] {
+    #[inline]
+    fn from_rgbe(r: u8, g: u8, b: u8, e: u8) -> Self {
+        let RGB { r, g, b } = RGBE { r, g, b, e }.into();
+        [r, g, b, 1.0]
+    }
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`] into pixels of any type
+/// that implements [`FromRgbe`], returning its width, height and pixels.
+///
+/// Each scanline is decoded into its stored bytes and converted as soon as it is done, so the
+/// image is never held as [`RGB`] first. The pixels are in rows from top to bottom, like
+/// [`Image::data`].
+pub fn load_with<P: FromRgbe, R: BufRead>(reader: R) -> LoadResult<(usize, usize, Vec<P>)> {
+    let (_, resolution, reader) = read_preamble(reader)?;
+    let data = decode_interleaved(reader, resolution, |&[r, g, b, e]: &[u8; 4]| {
+        [P::from_rgbe(r, g, b, e)]
+    })?;
+    Ok((resolution.width, resolution.height, data))
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`] as interleaved RGBA
+/// floats, returning its width, height and `width * height * 4` values.
+///
+/// Every pixel gets the same `alpha`. It is added while each scanline is copied out of the
+/// decoder, so this is cheaper than calling [`load`] and converting the image afterwards.
+pub fn load_rgba_f32<R: BufRead>(reader: R, alpha: f32) -> LoadResult<(usize, usize, Vec<f32>)> {
+    let (_, resolution, reader) = read_preamble(reader)?;
+    let data = decode_interleaved(reader, resolution, |pixel: &RGB| {
+        [pixel.r, pixel.g, pixel.b, alpha]
+    })?;
+    Ok((resolution.width, resolution.height, data))
+}
+
+/// Decode the pixel data into `N` values per pixel, converting each scanline from pixels of type
+/// `S` as soon as it has been decoded.
+fn decode_interleaved<R: BufRead, S: DecodedPixel, T: Copy, const N: usize>(
+    mut reader: R,
+    resolution: dim_parser::Resolution,
+    convert: impl Fn(&S) -> [T; N],
+) -> LoadResult<Vec<T>> {
+    let dim_parser::Resolution {
+        width,
+        height,
+        orientation,
+    } = resolution;
+
+    let length = width.checked_mul(height).ok_or(LoadError::FileFormat)?;
+    let mut data = Vec::<[T; N]>::with_capacity(length);
+
+    if length > 0 {
+        let mut scanline = vec![S::default(); orientation.scanline_len(width, height)];
+        let mut run = OldRun::new(length);
+        for _ in 0..length / scanline.len() {
+            decrunch(&mut reader, &mut scanline, &mut run)?;
+            data.extend(scanline.iter().map(&convert));
+        }
+        orientation.normalize(&mut data, width, height);
+    }
+
+    let mut data = std::mem::ManuallyDrop::new(data);
+    // SAFETY: [T; N] has the alignment of T and holds exactly N of them, so the allocation is
+    // valid for N times as many values of T. It is never touched through `data` again.
+    Ok(unsafe {
+        Vec::from_raw_parts(
+            data.as_mut_ptr().cast::<T>(),
+            data.len() * N,
+            data.capacity() * N,
+        )
+    })
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`], keeping the stored
+/// bytes of each pixel instead of converting them to floats.
+///
+/// Run-length encoding is still undone, so every pixel is available, but the mantissas and
+/// exponent are exactly as they were in the file.
+pub fn load_rgbe<R: BufRead>(reader: R) -> LoadResult<ImageRgbe> {
+    let (header, resolution, reader) = read_preamble(reader)?;
+    let data = decode_data(reader, resolution, &LoadLimits::none(), |_, _| Ok(()))?;
+    Ok(ImageRgbe {
+        width: resolution.width,
+        height: resolution.height,
+        orientation: resolution.orientation,
+        color_format: header.format.unwrap_or_default(),
+        data,
+    })
+}
+
+/// How far along [`load_with_progress`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeProgress {
+    /// How many scanlines have been decoded.
+    pub rows_done: usize,
+    /// How many scanlines there are in total.
+    pub rows_total: usize,
+    /// How many bytes have been read, including the header.
+    pub bytes_read: u64,
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`], calling `progress` after
+/// each scanline is decoded.
+///
+/// The callback is only given a copy of the progress, so it cannot interfere with decoding.
+pub fn load_with_progress<R, F>(reader: R, mut progress: F) -> LoadResult<Image>
+where
+    R: BufRead,
+    F: FnMut(DecodeProgress),
+{
+    let mut reader = CountingReader {
+        inner: reader,
+        count: 0,
+    };
+    let (header, resolution, reader) = read_preamble(&mut reader)?;
+    let rows_total = resolution.scanlines();
+
+    let (image, _) = decode_pixels(
+        reader,
+        header,
+        resolution,
+        &LoadLimits::none(),
+        |rows_done, reader| {
+            progress(DecodeProgress {
+                rows_done,
+                rows_total,
+                bytes_read: reader.count,
+            });
+            Ok(())
+        },
+    )?;
+    Ok(image)
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`], stopping with
+/// [`LoadError::Cancelled`] once `cancel` is set.
+///
+/// The flag is checked after every scanline, so another thread can set it to abandon a decode
+/// that is no longer needed. The partially decoded pixels are dropped.
+pub fn load_cancellable<R: BufRead>(reader: R, cancel: &AtomicBool) -> LoadResult<Image> {
+    let (header, resolution, reader) = read_preamble(reader)?;
+    let (image, _) = decode_pixels(reader, header, resolution, &LoadLimits::none(), |_, _| {
+        if cancel.load(Ordering::Relaxed) {
+            Err(LoadError::Cancelled)
+        } else {
+            Ok(())
+        }
+    })?;
+    Ok(image)
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`], and report how many bytes
+/// it took up.
+pub fn load_counted<R: BufRead>(reader: R) -> LoadResult<(Image, u64)> {
+    let mut reader = CountingReader {
+        inner: reader,
+        count: 0,
+    };
+    let image = load(&mut reader)?;
+    Ok((image, reader.count))
+}
+
+/// An image that could only be partly decoded, returned by [`load_partial`].
+#[derive(Debug)]
+pub struct PartialImage {
+    /// The image, with every pixel that could not be decoded left black.
+    pub image: Image,
+    /// How many whole scanlines were decoded. Unless the image is stored in an unusual
+    /// [`Orientation`], these are the top rows.
+    pub rows_decoded: usize,
+    /// The error that stopped decoding.
+    pub error: LoadError,
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`], keeping what was decoded
+/// if the data ends early or is corrupt.
+///
+/// If decoding fails with [`LoadError::Eof`] or [`LoadError::Rle`] after at least one scanline
+/// was complete, a [`PartialImage`] is returned instead of the error. Any other error, or one
+/// before the first scanline is complete, is returned as is.
+pub fn load_partial<R: BufRead>(reader: R) -> LoadResult<Result<Image, PartialImage>> {
+    let (header, resolution, mut reader) = read_preamble(reader)?;
+    let dim_parser::Resolution {
+        width,
+        height,
+        orientation,
+    } = resolution;
+
+    let length = width.checked_mul(height).ok_or(LoadError::FileFormat)?;
+
+    let black = RGB::default();
+    let mut data = vec![black; length];
+    let mut rows_decoded = 0;
+    let mut error = None;
+
+    if length > 0 {
+        let scanline_len = orientation.scanline_len(width, height);
+        let mut run = OldRun::new(length);
+        for scanline in data.chunks_mut(scanline_len) {
+            if let Err(e) = decrunch(&mut reader, scanline, &mut run) {
+                scanline.fill(black);
+                error = Some(e);
+                break;
+            }
+            rows_decoded += 1;
+        }
+        orientation.normalize(&mut data, width, height);
+    }
+
+    let image = Image {
+        width,
+        height,
+        orientation,
+        color_format: header.format.unwrap_or_default(),
+        pixel_aspect: header.pixel_aspect.unwrap_or(1.0),
+        data,
+    };
+    match error {
+        None => Ok(Ok(image)),
+        Some(error @ (LoadError::Eof(_) | LoadError::Rle)) if rows_decoded > 0 => {
+            Ok(Err(PartialImage {
+                image,
+                rows_decoded,
+                error,
+            }))
+        }
+        Some(error) => Err(error),
+    }
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`], skipping over scanlines
+/// with invalid run-length encoding.
+///
+/// When a scanline fails to decode, the data is searched for the start of the next scanline
+/// with the adaptive run-length encoding, and decoding continues from there. Damaged scanlines
+/// are left black, and their indices in the order they are stored are returned alongside the
+/// image. For the standard [`Orientation`] these are rows counted from the top. If no scanline
+/// can be found after a damaged one, every remaining scanline is reported as damaged.
+///
+/// Only images whose scanlines can use the adaptive encoding can be repaired. For others, and
+/// for any error other than [`LoadError::Rle`], this fails like [`load`].
+pub fn load_repaired<R: BufRead>(reader: R) -> LoadResult<(Image, Vec<usize>)> {
+    let (header, resolution, mut reader) = read_preamble(reader)?;
+    let dim_parser::Resolution {
+        width,
+        height,
+        orientation,
+    } = resolution;
+
+    let length = width.checked_mul(height).ok_or(LoadError::FileFormat)?;
+
+    let black = RGB::default();
+    let mut data = vec![black; length];
+    let mut damaged = Vec::new();
+
+    if length > 0 {
+        let scanline_len = orientation.scanline_len(width, height);
+        let marker = [2, 2, (scanline_len >> 8) as u8, scanline_len as u8];
+        let repairable = NEW_RLE_LEN.contains(&scanline_len);
+
+        // Whether the marker of the next scanline has already been consumed while searching
+        let mut found_marker = false;
+        let mut exhausted = false;
+        let mut run = OldRun::new(length);
+        for (index, scanline) in data.chunks_mut(scanline_len).enumerate() {
+            if exhausted {
+                damaged.push(index);
+                continue;
+            }
+
+            let prefix: &[u8] = if found_marker { &marker } else { &[] };
+            match decrunch(prefix.chain(&mut reader), scanline, &mut run) {
+                Ok(_) => found_marker = false,
+                Err(LoadError::Rle) if repairable => {
+                    scanline.fill(black);
+                    damaged.push(index);
+                    // The next scanline found is in the new format
+                    run.reset();
+                    run.end_scanline(scanline_len);
+                    found_marker = skip_past(&mut reader, &marker)?;
+                    exhausted = !found_marker;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        orientation.normalize(&mut data, width, height);
+    }
+
+    let image = Image {
+        width,
+        height,
+        orientation,
+        color_format: header.format.unwrap_or_default(),
+        pixel_aspect: header.pixel_aspect.unwrap_or(1.0),
+        data,
+    };
+    Ok((image, damaged))
+}
+
+/// Consume bytes up to and including the next occurrence of `marker`. Returns `false` if the
+/// stream ended first.
+fn skip_past<R: BufRead>(reader: &mut R, marker: &[u8; 4]) -> LoadResult<bool> {
+    let mut window = [0u8; 4];
+    let mut seen = 0;
+    loop {
+        let byte = match reader.read_byte() {
+            Ok(byte) => byte,
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => return Ok(false),
+            Err(error) => return Err(error.into()),
+        };
+        window.copy_within(1.., 0);
+        window[3] = byte;
+        seen += 1;
+        if seen >= window.len() && window == *marker {
+            return Ok(true);
+        }
+    }
+}
+
+/// The result of checking a Radiance HDR image with [`validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationReport {
+    /// The width of the image, in pixels.
+    pub width: usize,
+    /// The height of the image, in pixels.
+    pub height: usize,
+    /// The order the pixels are stored in.
+    pub orientation: Orientation,
+    /// How each scanline is encoded, in the order they are stored.
+    pub scanlines: Vec<ScanlineFormat>,
+}
+
+/// Check that a reader that implements [`BufRead`] holds a valid Radiance HDR image, without
+/// keeping the decoded pixels.
+///
+/// Every scanline is decoded into the same buffer, so memory use is proportional to the width
+/// of the image rather than its area. Any error [`load`] would return is returned here as well.
+pub fn validate<R: BufRead>(reader: R) -> LoadResult<ValidationReport> {
+    let (_, resolution, reader) = read_preamble(reader)?;
+
+    let mut scanlines = Vec::new();
+    stream_scanlines(reader, &resolution, |_, format, _| {
+        scanlines.push(format);
+        Ok(())
+    })?;
+
+    Ok(ValidationReport {
+        width: resolution.width,
+        height: resolution.height,
+        orientation: resolution.orientation,
+        scanlines,
+    })
+}
+
+/// Decode every scanline into the same buffer, calling `scanline_done` with the index, format and
+/// pixels of each one. Decoding stops if it returns an error.
+///
+/// Memory use is proportional to the length of a scanline, however large the image is.
+fn stream_scanlines<R: BufRead>(
+    mut reader: R,
+    resolution: &dim_parser::Resolution,
+    mut scanline_done: impl FnMut(usize, ScanlineFormat, &[RGB]) -> LoadResult,
+) -> LoadResult {
+    let dim_parser::Resolution {
+        width,
+        height,
+        orientation,
+    } = *resolution;
+    if width.checked_mul(height).is_none() {
+        return Err(LoadError::FileFormat);
+    }
+
+    let mut scanline = vec![RGB::default(); orientation.scanline_len(width, height)];
+    let mut run = OldRun::new(width * height);
+    for index in 0..resolution.scanlines() {
+        let format = decrunch(&mut reader, &mut scanline, &mut run)?;
+        scanline_done(index, format, &scanline)?;
+    }
+    Ok(())
+}
+
+/// An iterator over the images in a stream of Radiance HDR images stored back to back. Created
+/// with [`decode_all`].
+#[derive(Debug)]
+pub struct MultiDecoder<R> {
+    reader: R,
+    done: bool,
+}
+
+impl<R: BufRead> Iterator for MultiDecoder<R> {
+    type Item = LoadResult<Image>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = match self.reader.fill_buf() {
+            Ok([]) => {
+                self.done = true;
+                return None;
+            }
+            Ok(_) => load(&mut self.reader),
+            Err(error) => Err(error.into()),
+        };
+
+        self.done = result.is_err();
+        Some(result)
+    }
+}
+
+impl<R: BufRead> std::iter::FusedIterator for MultiDecoder<R> {}
+
+/// Decode every image in a stream of Radiance HDR images stored back to back.
+///
+/// The iterator ends when the stream ends between two images. If it ends partway through an
+/// image, or any other error occurs, the error is yielded and the iterator ends after it.
+pub fn decode_all<R: BufRead>(reader: R) -> MultiDecoder<R> {
+    MultiDecoder {
+        reader,
+        done: false,
+    }
+}
+
+/// Load a Radiance HDR image from a reader that only implements [`Read`].
+///
+/// The reader is wrapped in a [`BufReader`] internally, so it may be read past the end of the
+/// image. Prefer [`load`] if the reader already implements [`BufRead`].
+pub fn load_unbuffered<R: Read>(reader: R) -> LoadResult<Image> {
+    load(BufReader::new(reader))
+}
+
+/// Load a Radiance HDR image that may be gzip-compressed from a reader that implements
+/// [`BufRead`].
+///
+/// Compressed data is recognized by its magic bytes and decompressed on the fly. Anything else is
+/// passed straight to [`load`].
+#[cfg(feature = "gzip")]
+pub fn load_gz<R: BufRead>(reader: R) -> LoadResult<Image> {
+    load_gz_with_limits(reader, &LoadLimits::header_only())
+}
+
+/// Like [`load_gz`], but with the `limits` of [`load_with_limits`].
+#[cfg(feature = "gzip")]
+fn load_gz_with_limits<R: BufRead>(mut reader: R, limits: &LoadLimits) -> LoadResult<Image> {
+    if reader.fill_buf()?.starts_with(GZIP_MAGIC) {
+        let reader = BufReader::new(flate2::bufread::MultiGzDecoder::new(reader));
+        load_with_limits(reader, limits)
+    } else {
+        load_with_limits(reader, limits)
+    }
+}
+
+/// Load a Radiance HDR image from a file on disk.
+///
+/// The file is opened and read through a [`BufReader`] with a 64 KiB buffer. The file extension
+/// is not inspected, so `.hdr` and `.pic` files are treated the same. With the `gzip` feature
+/// enabled, gzip-compressed files are decompressed as well. Errors from opening the
+/// file are converted like any other [`std::io::Error`], so a missing file is reported as
+/// [`LoadError::Io`] and stays distinct from a [`LoadError::FileFormat`] caused by the contents
+/// of the file.
+pub fn load_path<P: AsRef<Path>>(path: P) -> LoadResult<Image> {
+    load_path_with_limits(path, &LoadLimits::header_only())
+}
+
+/// Like [`load_path`], but fail with [`LoadError::LimitExceeded`] if the image or its header is
+/// larger than `limits` allow, like [`load_with_limits`].
+pub fn load_path_with_limits<P: AsRef<Path>>(path: P, limits: &LoadLimits) -> LoadResult<Image> {
+    const BUFFER_SIZE: usize = 64 * 1024;
+
+    let file = File::open(path)?;
+    let reader = BufReader::with_capacity(BUFFER_SIZE, file);
+
+    #[cfg(feature = "gzip")]
+    return load_gz_with_limits(reader, limits);
+
+    #[cfg(not(feature = "gzip"))]
+    load_with_limits(reader, limits)
+}
+
+/// Load several Radiance HDR images from files on disk in parallel, using [`load_path`] for each.
+///
+/// The results are in the same order as `paths`. A file that fails to load does not affect the
+/// others.
+#[cfg(feature = "rayon")]
+pub fn load_many<P: AsRef<Path> + Sync>(paths: &[P]) -> Vec<LoadResult<Image>> {
+    load_many_with_limits(paths, &LoadLimits::header_only())
+}
+
+/// Like [`load_many`], but hold each file to `limits`, using [`load_path_with_limits`].
+#[cfg(feature = "rayon")]
+pub fn load_many_with_limits<P: AsRef<Path> + Sync>(
+    paths: &[P],
+    limits: &LoadLimits,
+) -> Vec<LoadResult<Image>> {
+    use rayon::prelude::*;
+
+    paths
+        .par_iter()
+        .map(|path| load_path_with_limits(path, limits))
+        .collect()
+}
+
+/// Load a Radiance HDR image by memory-mapping a file on disk.
+///
+/// The mapped bytes are read through the same [`BufRead`] decoder as [`load`], in place of a
+/// [`File`] wrapped in a [`BufReader`]. Errors opening or mapping the file are returned as
+/// [`LoadError::Io`].
+///
+/// The file must not be modified by anyone while it is being loaded.
+#[cfg(feature = "mmap")]
+pub fn load_mmap<P: AsRef<Path>>(path: P) -> LoadResult<Image> {
+    let file = File::open(path)?;
+
+    // Mapping an empty file fails on some platforms, so skip straight to decoding nothing
+    if file.metadata()?.len() == 0 {
+        return load(&[][..]);
+    }
+
+    // SAFETY: the map is read-only and only lives for the duration of the decode. Concurrent
+    // modification of the file is documented as the caller's responsibility.
+    let map = unsafe { memmap2::Mmap::map(&file) }?;
+    load(&map[..])
 }
@@ -0,0 +1,118 @@
+use super::{LoadError, LoadResult, RGB};
+use std::fmt;
+
+/// A limit in [`LoadLimits`] that an image exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Limit {
+    /// [`LoadLimits::max_width`]
+    Width,
+    /// [`LoadLimits::max_height`]
+    Height,
+    /// [`LoadLimits::max_pixels`]
+    Pixels,
+    /// [`LoadLimits::max_alloc_bytes`]
+    AllocBytes,
+    /// [`LoadLimits::max_header_line_bytes`]
+    HeaderLineBytes,
+    /// [`LoadLimits::max_header_bytes`]
+    HeaderBytes,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Width => "width",
+            Self::Height => "height",
+            Self::Pixels => "pixel count",
+            Self::AllocBytes => "allocation size",
+            Self::HeaderLineBytes => "header line length",
+            Self::HeaderBytes => "header size",
+        })
+    }
+}
+
+/// Limits on the size of images to load, to guard against malicious files that claim huge
+/// dimensions in order to exhaust memory.
+///
+/// The limits on the image are checked against the resolution line, before any memory is
+/// allocated for the pixel data. The limits on the header are checked while it is read, so a file
+/// that never ends a line fails as soon as it has sent too many bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LoadLimits {
+    /// The largest width allowed, in pixels.
+    pub max_width: usize,
+    /// The largest height allowed, in pixels.
+    pub max_height: usize,
+    /// The largest number of pixels allowed.
+    pub max_pixels: usize,
+    /// The largest allocation allowed for the pixel data, in bytes.
+    pub max_alloc_bytes: usize,
+    /// The longest line allowed in the header, including the resolution line, in bytes without
+    /// the line ending.
+    pub max_header_line_bytes: usize,
+    /// The largest header allowed, from the start of the file to the end of the resolution line,
+    /// in bytes.
+    pub max_header_bytes: usize,
+}
+
+impl Default for LoadLimits {
+    /// Allow images up to 32768 pixels on each side, taking up at most 4 GiB, or the whole
+    /// address space on 32-bit targets, with headers of up to 1 MiB and lines of up to 8 KiB.
+    fn default() -> Self {
+        Self {
+            max_width: 32768,
+            max_height: 32768,
+            max_pixels: 32768 * 32768,
+            max_alloc_bytes: (4u64 << 30).min(usize::MAX as u64) as usize,
+            max_header_line_bytes: 8 << 10,
+            max_header_bytes: 1 << 20,
+        }
+    }
+}
+
+impl LoadLimits {
+    /// No limits at all, not even on the header.
+    pub fn none() -> Self {
+        Self {
+            max_width: usize::MAX,
+            max_height: usize::MAX,
+            max_pixels: usize::MAX,
+            max_alloc_bytes: usize::MAX,
+            max_header_line_bytes: usize::MAX,
+            max_header_bytes: usize::MAX,
+        }
+    }
+
+    /// No limits on the image, but the default ones on the header, which only a broken or
+    /// malicious file gets near. This is what [`load`](super::load) uses.
+    pub(crate) fn header_only() -> Self {
+        let default = Self::default();
+        Self {
+            max_header_line_bytes: default.max_header_line_bytes,
+            max_header_bytes: default.max_header_bytes,
+            ..Self::none()
+        }
+    }
+
+    /// Check the dimensions of an image against these limits.
+    pub(crate) fn check(&self, width: usize, height: usize) -> LoadResult {
+        let exceeded = |limit| Err(LoadError::LimitExceeded(limit));
+
+        if width > self.max_width {
+            return exceeded(Limit::Width);
+        }
+        if height > self.max_height {
+            return exceeded(Limit::Height);
+        }
+
+        let pixels = width.checked_mul(height).ok_or(LoadError::FileFormat)?;
+        if pixels > self.max_pixels {
+            return exceeded(Limit::Pixels);
+        }
+
+        match pixels.checked_mul(std::mem::size_of::<RGB>()) {
+            Some(bytes) if bytes <= self.max_alloc_bytes => Ok(()),
+            _ => exceeded(Limit::AllocBytes),
+        }
+    }
+}
@@ -0,0 +1,143 @@
+/// The order in which the pixels of a Radiance HDR image are stored, as given by its resolution
+/// line.
+///
+/// The first axis of the resolution line is the one that changes slowest, i.e. the one scanlines
+/// are laid out along. A `-` sign on the Y axis means the first scanline is at the top, and a `+`
+/// sign on the X axis means pixels go from left to right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Orientation {
+    /// `-Y H +X W`: rows from top to bottom, each from left to right. This is the standard
+    /// orientation, written by almost all software.
+    #[default]
+    NegYPosX,
+    /// `-Y H -X W`: rows from top to bottom, each from right to left.
+    NegYNegX,
+    /// `+Y H +X W`: rows from bottom to top, each from left to right.
+    PosYPosX,
+    /// `+Y H -X W`: rows from bottom to top, each from right to left.
+    PosYNegX,
+    /// `+X W -Y H`: columns from left to right, each from top to bottom.
+    PosXNegY,
+    /// `+X W +Y H`: columns from left to right, each from bottom to top.
+    PosXPosY,
+    /// `-X W -Y H`: columns from right to left, each from top to bottom.
+    NegXNegY,
+    /// `-X W +Y H`: columns from right to left, each from bottom to top.
+    NegXPosY,
+}
+
+impl Orientation {
+    /// Build an orientation from the two signed axes of a resolution line, in the order they
+    /// appear. Returns `None` if both name the same axis.
+    pub(crate) fn from_axes(first: (bool, u8), second: (bool, u8)) -> Option<Self> {
+        use Orientation::*;
+        Some(match (first, second) {
+            ((false, b'Y'), (true, b'X')) => NegYPosX,
+            ((false, b'Y'), (false, b'X')) => NegYNegX,
+            ((true, b'Y'), (true, b'X')) => PosYPosX,
+            ((true, b'Y'), (false, b'X')) => PosYNegX,
+            ((true, b'X'), (false, b'Y')) => PosXNegY,
+            ((true, b'X'), (true, b'Y')) => PosXPosY,
+            ((false, b'X'), (false, b'Y')) => NegXNegY,
+            ((false, b'X'), (true, b'Y')) => NegXPosY,
+            _ => return None,
+        })
+    }
+
+    /// Whether scanlines are columns rather than rows.
+    pub fn is_x_major(self) -> bool {
+        use Orientation::*;
+        matches!(self, PosXNegY | PosXPosY | NegXNegY | NegXPosY)
+    }
+
+    fn flips_x(self) -> bool {
+        use Orientation::*;
+        matches!(self, NegYNegX | PosYNegX | NegXNegY | NegXPosY)
+    }
+
+    fn flips_y(self) -> bool {
+        use Orientation::*;
+        matches!(self, PosYPosX | PosYNegX | PosXPosY | NegXPosY)
+    }
+
+    /// The number of pixels in each scanline of an image with the given dimensions.
+    pub(crate) fn scanline_len(self, width: usize, height: usize) -> usize {
+        if self.is_x_major() {
+            height
+        } else {
+            width
+        }
+    }
+
+    /// The x and y coordinate, counting from the top left, of pixel `i` of scanline `s` in an
+    /// image with the given dimensions.
+    pub(crate) fn position(
+        self,
+        s: usize,
+        i: usize,
+        width: usize,
+        height: usize,
+    ) -> (usize, usize) {
+        let (x, y) = if self.is_x_major() { (s, i) } else { (i, s) };
+        let x = if self.flips_x() { width - 1 - x } else { x };
+        let y = if self.flips_y() { height - 1 - y } else { y };
+        (x, y)
+    }
+
+    /// Rearrange pixel data stored in this orientation so that it is in rows from top to
+    /// bottom, each from left to right.
+    ///
+    /// Everything happens in place. Transposing X-major data needs one bit of scratch space per
+    /// pixel to keep track of which pixels have been moved.
+    pub(crate) fn normalize<T: Copy>(self, data: &mut [T], width: usize, height: usize) {
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        if self.is_x_major() {
+            transpose(data, width, height);
+        }
+
+        if self.flips_y() {
+            for row in 0..height / 2 {
+                let (top, bottom) = data.split_at_mut((height - 1 - row) * width);
+                top[row * width..(row + 1) * width].swap_with_slice(&mut bottom[..width]);
+            }
+        }
+
+        if self.flips_x() {
+            data.chunks_mut(width).for_each(<[T]>::reverse);
+        }
+    }
+}
+
+/// Transpose a matrix of `rows` by `cols` pixels in place, by following the cycles of the
+/// permutation.
+fn transpose<T: Copy>(data: &mut [T], rows: usize, cols: usize) {
+    let len = data.len();
+    if rows <= 1 || cols <= 1 {
+        return;
+    }
+
+    // The pixel at p moves to p * rows mod (len - 1), except for the last one, which stays put
+    let destination = |p: usize| (p as u128 * rows as u128 % (len - 1) as u128) as usize;
+
+    let mut moved = vec![0u64; len.div_ceil(64)];
+    for start in 1..len - 1 {
+        if moved[start / 64] & 1 << (start % 64) != 0 {
+            continue;
+        }
+
+        let mut carried = data[start];
+        let mut p = start;
+        loop {
+            p = destination(p);
+            std::mem::swap(&mut data[p], &mut carried);
+            moved[p / 64] |= 1 << (p % 64);
+            if p == start {
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,411 @@
+use super::{
+    check_magic, dim_parser, old_pixel, DecodedPixel, Header, Image, Limit, LoadError, LoadLimits,
+    LoadResult, OldRun, Orientation, MAGIC_PREFIX, NEW_RLE_LEN, RGB, RGBE,
+};
+use dim_parser::{CR, EOL};
+use std::io::{Error as IoError, ErrorKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Magic,
+    Header {
+        magic_line: bool,
+    },
+    Resolution,
+    ScanlineStart,
+    Old {
+        x: usize,
+    },
+    NewCode {
+        channel: usize,
+        x: usize,
+    },
+    NewRun {
+        channel: usize,
+        x: usize,
+        count: usize,
+    },
+    NewLiteral {
+        channel: usize,
+        x: usize,
+        left: usize,
+    },
+    Done,
+}
+
+/// The outcome of feeding a chunk of bytes to a [`PushDecoder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// How many bytes of the chunk were used. Bytes past the end of the image are left
+    /// unconsumed.
+    pub consumed: usize,
+    /// Whether the header has been parsed, so that the dimensions are known.
+    pub header_complete: bool,
+    /// Whether every scanline has been decoded, so that [`PushDecoder::finish`] will succeed.
+    pub complete: bool,
+}
+
+/// A decoder that is handed the image data in chunks, instead of reading it from a reader.
+///
+/// This is useful when the data arrives from a source that cannot be read from in a blocking
+/// fashion. Feed each chunk with [`feed`](Self::feed) as it arrives, then call
+/// [`finish`](Self::finish) to get the decoded [`Image`]. Once `feed` has returned an error the
+/// decoder should be dropped.
+///
+/// ```
+/// let bytes = b"#?RADIANCE\n\n-Y 1 +X 1\n\xff\x00\xff\x80";
+/// let mut decoder = radiant::PushDecoder::new();
+/// for chunk in bytes.chunks(3) {
+///     decoder.feed(chunk)?;
+/// }
+/// let image = decoder.finish()?;
+/// assert_eq!(image.width, 1);
+/// # Ok::<(), radiant::LoadError>(())
+/// ```
+#[derive(Debug)]
+pub struct PushDecoder {
+    state: State,
+    buf: Vec<u8>,
+    /// Whether the last line ended with a CR, so that an LF right after it belongs to it.
+    skip_lf: bool,
+    /// How many more bytes the header may take up, after the magic.
+    header_left: usize,
+    header: Header,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+    scanline_len: usize,
+    scanline: usize,
+    run: OldRun,
+    data: Vec<RGB>,
+}
+
+impl Default for PushDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PushDecoder {
+    /// Create a decoder that expects the start of a Radiance HDR file.
+    pub fn new() -> Self {
+        Self {
+            state: State::Magic,
+            buf: Vec::new(),
+            skip_lf: false,
+            header_left: LoadLimits::header_only().max_header_bytes - MAGIC_PREFIX.len(),
+            header: Header::default(),
+            width: 0,
+            height: 0,
+            orientation: Orientation::default(),
+            scanline_len: 0,
+            scanline: 0,
+            run: OldRun::new(0),
+            data: Vec::new(),
+        }
+    }
+
+    /// The width and height of the image, once the header has been parsed.
+    pub fn dimensions(&self) -> Option<(usize, usize)> {
+        if self.header_complete() {
+            Some((self.width, self.height))
+        } else {
+            None
+        }
+    }
+
+    /// Decode as much of `chunk` as possible.
+    ///
+    /// The whole chunk is consumed unless the image ends partway through it.
+    pub fn feed(&mut self, chunk: &[u8]) -> LoadResult<Progress> {
+        let mut consumed = 0;
+        while consumed < chunk.len() {
+            if self.skip_lf {
+                self.skip_lf = false;
+                if chunk[consumed] == EOL {
+                    consumed += 1;
+                    continue;
+                }
+            }
+            if self.state == State::Done {
+                break;
+            }
+            consumed += self.step(&chunk[consumed..])?;
+        }
+
+        Ok(Progress {
+            consumed,
+            header_complete: self.header_complete(),
+            complete: self.state == State::Done,
+        })
+    }
+
+    /// Return the decoded image, or [`LoadError::Eof`] if it has not been fed completely.
+    pub fn finish(self) -> LoadResult<Image> {
+        if self.state != State::Done {
+            return Err(LoadError::Eof(IoError::new(
+                ErrorKind::UnexpectedEof,
+                "image data ended unexpectedly",
+            )));
+        }
+
+        let mut data = self.data;
+        self.orientation
+            .normalize(&mut data, self.width, self.height);
+
+        Ok(Image {
+            width: self.width,
+            height: self.height,
+            orientation: self.orientation,
+            color_format: self.header.format.unwrap_or_default(),
+            pixel_aspect: self.header.pixel_aspect.unwrap_or(1.0),
+            data,
+        })
+    }
+
+    fn header_complete(&self) -> bool {
+        !matches!(
+            self.state,
+            State::Magic | State::Header { .. } | State::Resolution
+        )
+    }
+
+    /// Move bytes into `buf` until it holds `len` of them. Returns how many bytes were used and
+    /// whether `buf` is full.
+    fn collect(&mut self, input: &[u8], len: usize) -> (usize, bool) {
+        let count = (len - self.buf.len()).min(input.len());
+        self.buf.extend_from_slice(&input[..count]);
+        (count, self.buf.len() == len)
+    }
+
+    /// Move bytes of the header into `buf` up to the end of the line, without the line ending.
+    /// Returns how many bytes were used and whether the line is complete. Lines may end with LF,
+    /// CR LF or just CR, and are limited like in [`load`](super::load).
+    fn collect_line(&mut self, input: &[u8]) -> LoadResult<(usize, bool)> {
+        let (consumed, complete) = match input.iter().position(|&b| b == EOL || b == CR) {
+            Some(i) => {
+                self.buf.extend_from_slice(&input[..i]);
+                self.skip_lf = input[i] == CR;
+                (i + 1, true)
+            }
+            None => {
+                self.buf.extend_from_slice(input);
+                (input.len(), false)
+            }
+        };
+
+        if self.buf.len() > LoadLimits::header_only().max_header_line_bytes {
+            return Err(LoadError::LimitExceeded(Limit::HeaderLineBytes));
+        }
+        // Every line ending counts as one byte
+        self.header_left = self
+            .header_left
+            .checked_sub(consumed)
+            .ok_or(LoadError::LimitExceeded(Limit::HeaderBytes))?;
+        Ok((consumed, complete))
+    }
+
+    fn take_rgbe(&mut self) -> RGBE {
+        let rgbe = [self.buf[0], self.buf[1], self.buf[2], self.buf[3]];
+        self.buf.clear();
+        rgbe.into()
+    }
+
+    fn scanline(&mut self) -> &mut [RGB] {
+        let start = self.scanline * self.scanline_len;
+        &mut self.data[start..start + self.scanline_len]
+    }
+
+    /// Move on to the next scanline, filling in any run that continues into it and the ones
+    /// after it.
+    fn finish_scanline(&mut self) {
+        loop {
+            self.run.end_scanline(self.scanline_len);
+            self.scanline += 1;
+            if self.scanline * self.scanline_len == self.data.len() {
+                self.state = State::Done;
+                return;
+            }
+
+            let carried = self.run.start_scanline(self.scanline_len);
+            match self.run.last.filter(|_| carried > 0) {
+                Some(last) => {
+                    let from = RGB::from(last);
+                    self.scanline()[..carried]
+                        .iter_mut()
+                        .for_each(|to| *to = from);
+                    if carried < self.scanline_len {
+                        self.state = State::Old { x: carried };
+                        return;
+                    }
+                }
+                None => {
+                    self.state = State::ScanlineStart;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Decode the next pixel of an old-format scanline, `x` pixels of which are done.
+    fn old_pixel(&mut self, rgbe: RGBE, x: usize) -> LoadResult {
+        let start = self.scanline * self.scanline_len;
+        let scanline = &mut self.data[start..start + self.scanline_len];
+        let x = old_pixel(rgbe, scanline, x, &mut self.run)?;
+        if x == self.scanline_len {
+            self.finish_scanline();
+        } else {
+            self.state = State::Old { x };
+        }
+        Ok(())
+    }
+
+    fn finish_new(&mut self, channel: usize, x: usize) {
+        if x < self.scanline_len {
+            self.state = State::NewCode { channel, x };
+        } else if channel + 1 < RGB::CHANNELS.len() {
+            self.state = State::NewCode {
+                channel: channel + 1,
+                x: 0,
+            };
+        } else {
+            self.finish_scanline();
+        }
+    }
+
+    /// Decode a non-empty prefix of `input`, returning how many bytes were used.
+    fn step(&mut self, input: &[u8]) -> LoadResult<usize> {
+        match self.state {
+            State::Magic => {
+                let (consumed, full) = self.collect(input, MAGIC_PREFIX.len());
+                if full {
+                    check_magic(&self.buf)?;
+                    self.buf.clear();
+                    self.state = State::Header { magic_line: true };
+                }
+                Ok(consumed)
+            }
+            State::Header { magic_line } => {
+                let (consumed, complete) = self.collect_line(input)?;
+                if complete {
+                    if magic_line {
+                        self.header.program = Some(dim_parser::parse_program(&self.buf));
+                        self.state = State::Header { magic_line: false };
+                    } else if self.buf.is_empty() {
+                        // The header ends with an empty line
+                        self.header.check_format()?;
+                        self.state = State::Resolution;
+                    } else {
+                        self.header.push_line(&self.buf);
+                    }
+                    self.buf.clear();
+                }
+                Ok(consumed)
+            }
+            State::Resolution => {
+                let (consumed, complete) = self.collect_line(input)?;
+                if complete && self.buf.is_empty() {
+                    // Skip any more empty lines before the resolution
+                } else if complete {
+                    let resolution = dim_parser::expect_resolution_line(&self.buf)?;
+                    let dim_parser::Resolution {
+                        width,
+                        height,
+                        orientation,
+                    } = resolution;
+                    let length = width.checked_mul(height).ok_or(LoadError::FileFormat)?;
+                    self.buf.clear();
+                    self.width = width;
+                    self.height = height;
+                    self.orientation = orientation;
+                    self.scanline_len = orientation.scanline_len(width, height);
+                    self.run = OldRun::new(length);
+                    self.data = vec![RGB::default(); length];
+                    self.state = if length > 0 {
+                        State::ScanlineStart
+                    } else {
+                        State::Done
+                    };
+                }
+                Ok(consumed)
+            }
+            State::ScanlineStart => {
+                let (consumed, full) = self.collect(input, 4);
+                if full {
+                    let rgbe = self.take_rgbe();
+                    if NEW_RLE_LEN.contains(&self.scanline_len) && rgbe.is_new_decrunch_marker() {
+                        rgbe.check_scanline_len(self.scanline_len)?;
+                        self.run.reset();
+                        self.state = State::NewCode { channel: 0, x: 0 };
+                    } else {
+                        self.old_pixel(rgbe, 0)?;
+                    }
+                }
+                Ok(consumed)
+            }
+            State::Old { x } => {
+                let (consumed, full) = self.collect(input, 4);
+                if full {
+                    let rgbe = self.take_rgbe();
+                    self.old_pixel(rgbe, x)?;
+                }
+                Ok(consumed)
+            }
+            State::NewCode { channel, x } => {
+                let code = input[0] as usize;
+                if code > 128 {
+                    // run
+                    let count = code & 127;
+                    if x + count > self.scanline_len {
+                        return Err(LoadError::Rle);
+                    }
+                    self.state = State::NewRun { channel, x, count };
+                } else {
+                    // non-run
+                    self.state = State::NewLiteral {
+                        channel,
+                        x,
+                        left: code,
+                    };
+                    if code == 0 {
+                        self.finish_new(channel, x);
+                    }
+                }
+                Ok(1)
+            }
+            State::NewRun { channel, x, count } => {
+                let mutate_pixel = RGB::CHANNELS[channel];
+                let val = input[0];
+                for pixel in &mut self.scanline()[x..x + count] {
+                    mutate_pixel(pixel, val);
+                }
+                self.finish_new(channel, x + count);
+                Ok(1)
+            }
+            State::NewLiteral { channel, x, left } => {
+                let count = left.min(input.len());
+                let mutate_pixel = RGB::CHANNELS[channel];
+                let pixels = self
+                    .scanline()
+                    .get_mut(x..x + count)
+                    .ok_or(LoadError::Rle)?;
+
+                for (pixel, &val) in pixels.iter_mut().zip(&input[..count]) {
+                    mutate_pixel(pixel, val);
+                }
+
+                if count == left {
+                    self.finish_new(channel, x + count);
+                } else {
+                    self.state = State::NewLiteral {
+                        channel,
+                        x: x + count,
+                        left: left - count,
+                    };
+                }
+                Ok(count)
+            }
+            State::Done => Ok(0),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use super::{read_preamble, stream_scanlines, Image, LoadError, LoadResult, RGB};
+use std::io::BufRead;
+use std::num::NonZeroUsize;
+
+/// Load the part of a Radiance HDR image inside a rectangle, from a reader that implements
+/// [`BufRead`].
+///
+/// The rectangle starts at `x` and `y`, counting from the top left, and is `width` by `height`
+/// pixels. Every scanline still has to be decoded, but only the pixels inside the rectangle are
+/// kept, so memory use is proportional to the size of the rectangle rather than the image. If the
+/// rectangle does not fit inside the image, [`LoadError::InvalidRegion`] is returned.
+pub fn load_region<R: BufRead>(
+    reader: R,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+) -> LoadResult<Image> {
+    let (header, resolution, reader) = read_preamble(reader)?;
+
+    let fits =
+        |start: usize, len: usize, max: usize| start.checked_add(len).is_some_and(|end| end <= max);
+    if !fits(x, width, resolution.width) || !fits(y, height, resolution.height) {
+        return Err(LoadError::InvalidRegion);
+    }
+
+    let orientation = resolution.orientation;
+    let mut data = vec![RGB::default(); width * height];
+    stream_scanlines(reader, &resolution, |s, _, scanline| {
+        for (i, &pixel) in scanline.iter().enumerate() {
+            let (px, py) = orientation.position(s, i, resolution.width, resolution.height);
+            if (x..x + width).contains(&px) && (y..y + height).contains(&py) {
+                data[(py - y) * width + px - x] = pixel;
+            }
+        }
+        Ok(())
+    })?;
+
+    Ok(Image {
+        width,
+        height,
+        orientation,
+        color_format: header.format.unwrap_or_default(),
+        pixel_aspect: header.pixel_aspect.unwrap_or(1.0),
+        data,
+    })
+}
+
+/// Load a Radiance HDR image scaled down by `factor` in both directions, from a reader that
+/// implements [`BufRead`].
+///
+/// Only the top left pixel of every `factor` by `factor` block is kept, so the result is
+/// `ceil(width / factor)` by `ceil(height / factor)` pixels. Memory use is proportional to the
+/// size of the result plus one scanline. For a smoother result, see [`load_thumbnail`].
+pub fn load_downscaled<R: BufRead>(reader: R, factor: NonZeroUsize) -> LoadResult<Image> {
+    let (header, resolution, reader) = read_preamble(reader)?;
+    let factor = factor.get();
+    let width = resolution.width.div_ceil(factor);
+    let height = resolution.height.div_ceil(factor);
+
+    let orientation = resolution.orientation;
+    let mut data = vec![RGB::default(); width * height];
+    stream_scanlines(reader, &resolution, |s, _, scanline| {
+        for (i, &pixel) in scanline.iter().enumerate() {
+            let (x, y) = orientation.position(s, i, resolution.width, resolution.height);
+            if x % factor == 0 && y % factor == 0 {
+                data[y / factor * width + x / factor] = pixel;
+            }
+        }
+        Ok(())
+    })?;
+
+    Ok(Image {
+        width,
+        height,
+        orientation,
+        color_format: header.format.unwrap_or_default(),
+        pixel_aspect: header.pixel_aspect.unwrap_or(1.0),
+        data,
+    })
+}
+
+/// Load a Radiance HDR image scaled down so that neither side is longer than `max_dim`, from a
+/// reader that implements [`BufRead`].
+///
+/// The image is scaled down by the smallest whole factor that fits, and each pixel of the result
+/// is the average of the block of pixels it covers. Unlike [`load_downscaled`], small bright
+/// features like the sun are not lost. Blocks along the right and bottom edges may be smaller
+/// than the others. Memory use is proportional to the size of the result plus one scanline.
+///
+/// A `max_dim` of 0 is treated as 1.
+pub fn load_thumbnail<R: BufRead>(reader: R, max_dim: usize) -> LoadResult<Image> {
+    let (header, resolution, reader) = read_preamble(reader)?;
+    let longest = resolution.width.max(resolution.height);
+    let factor = longest.div_ceil(max_dim.max(1)).max(1);
+    let width = resolution.width.div_ceil(factor);
+    let height = resolution.height.div_ceil(factor);
+
+    let orientation = resolution.orientation;
+    let mut data = vec![RGB::default(); width * height];
+    stream_scanlines(reader, &resolution, |s, _, scanline| {
+        for (i, &pixel) in scanline.iter().enumerate() {
+            let (x, y) = orientation.position(s, i, resolution.width, resolution.height);
+            let sum = &mut data[y / factor * width + x / factor];
+            *sum = *sum + pixel;
+        }
+        Ok(())
+    })?;
+
+    // The number of pixels in the block covered by the given output row or column
+    let samples = |out: usize, len: usize| factor.min(len - out * factor) as f32;
+    for (y, row) in data.chunks_mut(width.max(1)).enumerate() {
+        let rows = samples(y, resolution.height);
+        for (x, pixel) in row.iter_mut().enumerate() {
+            *pixel = *pixel / (rows * samples(x, resolution.width));
+        }
+    }
+
+    Ok(Image {
+        width,
+        height,
+        orientation,
+        color_format: header.format.unwrap_or_default(),
+        pixel_aspect: header.pixel_aspect.unwrap_or(1.0),
+        data,
+    })
+}
+
+impl Image {
+    /// Scale the image down by `factor` in both directions, averaging each `factor` by `factor`
+    /// block of pixels into one, like [`load_thumbnail`] does.
+    ///
+    /// The result is `ceil(width / factor)` by `ceil(height / factor)` pixels. Blocks along the
+    /// right and bottom edges may be smaller than the others, and are averaged over the pixels
+    /// they do cover. Averaging happens on the linear values, as they are stored.
+    pub fn downsample_box(&self, factor: NonZeroUsize) -> Image {
+        let factor = factor.get();
+        let width = self.width.div_ceil(factor);
+        let height = self.height.div_ceil(factor);
+
+        let mut data = vec![RGB::default(); width * height];
+        for (x, y, &pixel) in self.enumerate_pixels() {
+            let sum = &mut data[y / factor * width + x / factor];
+            *sum = *sum + pixel;
+        }
+
+        // The number of pixels in the block covered by the given output row or column
+        let samples = |out: usize, len: usize| factor.min(len - out * factor) as f32;
+        for (y, row) in data.chunks_mut(width.max(1)).enumerate() {
+            let rows = samples(y, self.height);
+            for (x, pixel) in row.iter_mut().enumerate() {
+                *pixel = *pixel / (rows * samples(x, self.width));
+            }
+        }
+
+        Image {
+            width,
+            height,
+            orientation: self.orientation,
+            color_format: self.color_format,
+            pixel_aspect: self.pixel_aspect,
+            data,
+        }
+    }
+
+    /// Stretch the image so that its pixels are square, according to its
+    /// [`pixel_aspect`](Self::pixel_aspect), with bilinear filtering.
+    ///
+    /// Pixels taller than they are wide make the image taller, and pixels wider than they are
+    /// tall make it wider, so no detail is lost. The new size is rounded to whole pixels. If the
+    /// pixels are already square, the image is returned as it is. Either way, the pixel aspect of
+    /// the result is 1.0.
+    pub fn resample_to_square_pixels(&self) -> Image {
+        let aspect = self.pixel_aspect;
+        let stretch = |len: usize, factor: f32| ((len as f32 * factor).round() as usize).max(1);
+        let (width, height) = if aspect > 1.0 {
+            (self.width, stretch(self.height, aspect))
+        } else {
+            (stretch(self.width, 1.0 / aspect), self.height)
+        };
+
+        if self.data.is_empty() || (width, height) == (self.width, self.height) {
+            return Image {
+                width: self.width,
+                height: self.height,
+                orientation: self.orientation,
+                color_format: self.color_format,
+                pixel_aspect: 1.0,
+                data: self.data.clone(),
+            };
+        }
+        self.resize_bilinear(width, height)
+    }
+
+    /// Resample a non-empty image to `width` by `height` pixels with bilinear filtering.
+    fn resize_bilinear(&self, width: usize, height: usize) -> Image {
+        // The two source pixels to blend for each position along an axis, and the weight of the
+        // second one, lining up the centers of the first and last pixels
+        let samples = |from: usize, to: usize| -> Vec<(usize, usize, f32)> {
+            let scale = from as f32 / to as f32;
+            (0..to)
+                .map(|i| {
+                    let pos = ((i as f32 + 0.5) * scale - 0.5).clamp(0.0, (from - 1) as f32);
+                    let first = pos as usize;
+                    (first, (first + 1).min(from - 1), pos - first as f32)
+                })
+                .collect()
+        };
+        let lerp = |a: RGB, b: RGB, t: f32| a * (1.0 - t) + b * t;
+
+        let columns = samples(self.width, width);
+        let mut data = Vec::with_capacity(width * height);
+        for (top, bottom, ty) in samples(self.height, height) {
+            for &(left, right, tx) in &columns {
+                let upper = lerp(*self.pixel(left, top), *self.pixel(right, top), tx);
+                let lower = lerp(*self.pixel(left, bottom), *self.pixel(right, bottom), tx);
+                data.push(lerp(upper, lower, ty));
+            }
+        }
+
+        Image {
+            width,
+            height,
+            orientation: self.orientation,
+            color_format: self.color_format,
+            pixel_aspect: 1.0,
+            data,
+        }
+    }
+}
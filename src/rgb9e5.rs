@@ -0,0 +1,68 @@
+use super::{decode_interleaved, read_preamble, LoadResult, RGB};
+use std::io::BufRead;
+
+/// Bits in each mantissa.
+const MANTISSA_BITS: i32 = 9;
+/// Added to the stored exponent.
+const EXPONENT_BIAS: i32 = 15;
+/// The largest stored exponent.
+const MAX_EXPONENT: i32 = 31;
+/// The largest value any channel can hold, 65408.
+const MAX_VALUE: f32 = 65408.0;
+
+/// Clamp a channel to the range the format can hold. NaN becomes zero.
+fn clamp(v: f32) -> f32 {
+    if v > 0.0 {
+        v.min(MAX_VALUE)
+    } else {
+        0.0
+    }
+}
+
+/// Pack a color into the shared-exponent `RGB9E5` format used by GPUs, e.g. Vulkan's
+/// `E5B9G9R9_UFLOAT_PACK32`: red in the lowest 9 bits, then green and blue, and the exponent in
+/// the top 5.
+///
+/// This follows the conversion in `EXT_texture_shared_exponent`. Negative values and NaN become
+/// zero, and values above 65408 are clamped to it.
+pub fn rgb_to_rgb9e5(pixel: RGB) -> u32 {
+    let [r, g, b] = [clamp(pixel.r), clamp(pixel.g), clamp(pixel.b)];
+    let max = r.max(g).max(b);
+
+    // The exponent of the largest channel, read from its bits so that it is exact
+    let floor_log2 = ((max.to_bits() >> 23) & 0xff) as i32 - 127;
+    let mut exponent = floor_log2.max(-EXPONENT_BIAS - 1) + 1 + EXPONENT_BIAS;
+
+    let mut scale = 2_f32.powi(exponent - EXPONENT_BIAS - MANTISSA_BITS);
+    if (max / scale + 0.5).floor() as i32 == 1 << MANTISSA_BITS {
+        exponent += 1;
+        scale *= 2.0;
+    }
+    debug_assert!(exponent <= MAX_EXPONENT);
+
+    let mantissa = |v: f32| (v / scale + 0.5).floor() as u32;
+    mantissa(r) | mantissa(g) << 9 | mantissa(b) << 18 | (exponent as u32) << 27
+}
+
+/// Unpack a color from the `RGB9E5` format, the inverse of [`rgb_to_rgb9e5`].
+pub fn rgb9e5_to_rgb(packed: u32) -> RGB {
+    let exponent = (packed >> 27) as i32;
+    let scale = 2_f32.powi(exponent - EXPONENT_BIAS - MANTISSA_BITS);
+    let mantissa = |shift: u32| ((packed >> shift) & 0x1ff) as f32 * scale;
+    RGB {
+        r: mantissa(0),
+        g: mantissa(9),
+        b: mantissa(18),
+    }
+}
+
+/// Load a Radiance HDR image from a reader that implements [`BufRead`] as packed `RGB9E5`
+/// texels, returning its width, height and one value per pixel.
+///
+/// Each scanline is packed as soon as it has been decoded. See [`rgb_to_rgb9e5`] for how values
+/// are converted.
+pub fn load_rgb9e5<R: BufRead>(reader: R) -> LoadResult<(usize, usize, Vec<u32>)> {
+    let (_, resolution, reader) = read_preamble(reader)?;
+    let data = decode_interleaved(reader, resolution, |&pixel: &RGB| [rgb_to_rgb9e5(pixel)])?;
+    Ok((resolution.width, resolution.height, data))
+}
@@ -0,0 +1,45 @@
+use crate::RGB;
+use std::simd::prelude::*;
+use std::simd::simd_swizzle;
+
+const LANES: usize = 8;
+
+/// The factor each channel is multiplied by for each exponent, the same as
+/// `2_f32.powi(e - 128) / 255.0` but built directly from the bits of the float.
+fn scales(exponents: u8x8) -> f32x8 {
+    let e = exponents.cast::<u32>();
+    let normal = (e - Simd::splat(1)) << Simd::splat(23);
+    // 2^-127 is subnormal, and 2^-128 becomes zero with `powi`
+    let small = e
+        .simd_eq(Simd::splat(1))
+        .select(Simd::splat(1 << 22), Simd::splat(0));
+    let bits = e.simd_lt(Simd::splat(2)).select(small, normal);
+    f32x8::from_bits(bits) / Simd::splat(255.0)
+}
+
+/// Multiply each pixel of a scanline by its exponent, eight pixels at a time.
+pub(crate) fn apply_exposure(scanline: &mut [RGB], exponents: &[u8]) {
+    let mut pixels = scanline.chunks_exact_mut(LANES);
+    let mut chunks = exponents.chunks_exact(LANES);
+
+    for (pixels, exponents) in (&mut pixels).zip(&mut chunks) {
+        // SAFETY: RGB is repr(C) with three f32 fields and no padding.
+        let floats: &mut [f32] =
+            unsafe { std::slice::from_raw_parts_mut(pixels.as_mut_ptr().cast(), LANES * 3) };
+
+        // Repeat each factor three times, once per channel
+        let d = scales(u8x8::from_slice(exponents));
+        let factors = [
+            simd_swizzle!(d, [0, 0, 0, 1, 1, 1, 2, 2]),
+            simd_swizzle!(d, [2, 3, 3, 3, 4, 4, 4, 5]),
+            simd_swizzle!(d, [5, 5, 6, 6, 6, 7, 7, 7]),
+        ];
+        for (floats, factor) in floats.chunks_exact_mut(LANES).zip(factors) {
+            (f32x8::from_slice(floats) * factor).copy_to_slice(floats);
+        }
+    }
+
+    for (pixel, &e) in pixels.into_remainder().iter_mut().zip(chunks.remainder()) {
+        pixel.apply_exposure(e);
+    }
+}
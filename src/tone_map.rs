@@ -0,0 +1,75 @@
+use super::{Image, RGB};
+
+/// Added to each luminance before taking its logarithm, so that black pixels don't pull the
+/// log-average down to zero.
+const LOG_DELTA: f32 = 1e-4;
+
+/// Encode a linear value in `[0, 1]` with the sRGB transfer function, as an 8-bit value.
+fn srgb8(v: f32) -> u8 {
+    let v = v.clamp(0.0, 1.0);
+    let encoded = if v <= 0.003_130_8 {
+        12.92 * v
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    (encoded * 255.0).round() as u8
+}
+
+impl Image {
+    /// The geometric mean of the luminance of every pixel, or 0 if the image has no pixels.
+    fn log_average_luminance(&self) -> f32 {
+        if self.data.is_empty() {
+            return 0.0;
+        }
+
+        let sum: f64 = self
+            .data
+            .iter()
+            .map(|pixel| f64::from((LOG_DELTA + pixel.luminance().max(0.0)).ln()))
+            .sum();
+        (sum / self.data.len() as f64).exp() as f32
+    }
+
+    /// Convert to 8-bit sRGB for display, using global Reinhard tone mapping.
+    ///
+    /// The image is scaled so that its log-average luminance maps to `key`, the middle grey of
+    /// the result. 0.18 is a typical choice; lower values give a darker image. Each pixel's
+    /// luminance `L` is then compressed to `L / (1 + L)`, keeping its hue.
+    ///
+    /// The pixels are returned in the same order as [`data`](Self::data).
+    pub fn tone_map_reinhard(&self, key: f32) -> Vec<[u8; 3]> {
+        let average = self.log_average_luminance();
+        let scale = if average > 0.0 { key / average } else { 0.0 };
+
+        self.data
+            .iter()
+            .map(|pixel| {
+                let luminance = pixel.luminance() * scale;
+                let factor = if luminance > 0.0 {
+                    scale / (1.0 + luminance)
+                } else {
+                    0.0
+                };
+                let RGB { r, g, b } = *pixel;
+                [srgb8(r * factor), srgb8(g * factor), srgb8(b * factor)]
+            })
+            .collect()
+    }
+
+    /// Convert to 8-bit RGBA for quick previews, with a simple exposure and gamma adjustment.
+    ///
+    /// Each channel is multiplied by `2^exposure`, raised to the power of `1 / gamma`, clamped
+    /// to `[0, 1]` and scaled to 255. Alpha is always 255. The result holds four bytes per pixel,
+    /// in the same order as [`data`](Self::data).
+    pub fn to_rgba8(&self, exposure: f32, gamma: f32) -> Vec<u8> {
+        let scale = 2_f32.powf(exposure);
+        let inv_gamma = 1.0 / gamma;
+        let encode = |v: f32| ((v * scale).powf(inv_gamma).clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        let mut rgba = Vec::with_capacity(self.data.len() * 4);
+        for &RGB { r, g, b } in &self.data {
+            rgba.extend_from_slice(&[encode(r), encode(g), encode(b), 255]);
+        }
+        rgba
+    }
+}
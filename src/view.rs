@@ -0,0 +1,164 @@
+/// The projection of a [`ViewParams`], as given by its `-vt` option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ViewType {
+    /// `-vtv`: a perspective view.
+    #[default]
+    Perspective,
+    /// `-vtl`: a parallel projection.
+    Parallel,
+    /// `-vtc`: a cylindrical panorama.
+    Cylindrical,
+    /// `-vth`: a hemispherical fisheye.
+    Hemispherical,
+    /// `-vta`: an angular fisheye.
+    Angular,
+    /// `-vts`: a planisphere, or stereographic, fisheye.
+    Planisphere,
+}
+
+impl ViewType {
+    fn from_letter(letter: &str) -> Option<Self> {
+        Some(match letter {
+            "v" => Self::Perspective,
+            "l" => Self::Parallel,
+            "c" => Self::Cylindrical,
+            "h" => Self::Hemispherical,
+            "a" => Self::Angular,
+            "s" => Self::Planisphere,
+            _ => return None,
+        })
+    }
+}
+
+/// The camera an image was rendered with, as given by the `VIEW` variable in the header.
+///
+/// Each `VIEW` line only sets the options it lists, on top of the lines before it, so options
+/// that are never set keep the defaults Radiance uses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewParams {
+    /// `-vt`: the projection.
+    pub view_type: ViewType,
+    /// `-vp`: the position of the camera.
+    pub viewpoint: [f32; 3],
+    /// `-vd`: the direction the camera faces.
+    pub direction: [f32; 3],
+    /// `-vu`: the direction that is up.
+    pub up: [f32; 3],
+    /// `-vh`: the horizontal size of the view, in degrees, or in world units for parallel
+    /// projections.
+    pub horizontal_size: f32,
+    /// `-vv`: the vertical size of the view, like `horizontal_size`.
+    pub vertical_size: f32,
+    /// `-vo`: the distance of the fore clipping plane.
+    pub fore_clip: f32,
+    /// `-va`: the distance of the aft clipping plane, or 0 for none.
+    pub aft_clip: f32,
+    /// `-vs`: how far the view is shifted to the right, as a fraction of its width.
+    pub shift: f32,
+    /// `-vl`: how far the view is lifted up, as a fraction of its height.
+    pub lift: f32,
+    /// Options that are not understood, like `-vf` or ones with values that could not be parsed,
+    /// each with its arguments, in order.
+    pub other: Vec<String>,
+}
+
+impl Default for ViewParams {
+    /// The view Radiance uses when none is given.
+    fn default() -> Self {
+        Self {
+            view_type: ViewType::Perspective,
+            viewpoint: [0.0, 0.0, 0.0],
+            direction: [0.0, 1.0, 0.0],
+            up: [0.0, 0.0, 1.0],
+            horizontal_size: 45.0,
+            vertical_size: 45.0,
+            fore_clip: 0.0,
+            aft_clip: 0.0,
+            shift: 0.0,
+            lift: 0.0,
+            other: Vec::new(),
+        }
+    }
+}
+
+impl ViewParams {
+    /// Apply the options in the value of a `VIEW` line, in any order.
+    pub(crate) fn apply(&mut self, value: &str) {
+        let words: Vec<&str> = value.split_ascii_whitespace().collect();
+        let mut rest = &words[..];
+        while let Some((&option, after)) = rest.split_first() {
+            // An option takes every word up to the next option. Negative numbers are not options.
+            let len = after
+                .iter()
+                .position(|word| is_option(word))
+                .unwrap_or(after.len());
+            let (args, next) = after.split_at(len);
+            if !self.apply_option(option, args) {
+                self.other.push(words_to_string(option, args));
+            }
+            rest = next;
+        }
+    }
+
+    /// Apply a single option, returning whether it was understood.
+    fn apply_option(&mut self, option: &str, args: &[&str]) -> bool {
+        let vector = |target: &mut [f32; 3]| match parse_floats(args) {
+            Some([x, y, z]) => {
+                *target = [x, y, z];
+                true
+            }
+            None => false,
+        };
+        let scalar = |target: &mut f32| match parse_floats(args) {
+            Some([value]) => {
+                *target = value;
+                true
+            }
+            None => false,
+        };
+
+        match option.strip_prefix("-v") {
+            Some("p") => vector(&mut self.viewpoint),
+            Some("d") => vector(&mut self.direction),
+            Some("u") => vector(&mut self.up),
+            Some("h") => scalar(&mut self.horizontal_size),
+            Some("v") => scalar(&mut self.vertical_size),
+            Some("o") => scalar(&mut self.fore_clip),
+            Some("a") => scalar(&mut self.aft_clip),
+            Some("s") => scalar(&mut self.shift),
+            Some("l") => scalar(&mut self.lift),
+            Some(t) => match (t.strip_prefix('t').and_then(ViewType::from_letter), args) {
+                (Some(view_type), []) => {
+                    self.view_type = view_type;
+                    true
+                }
+                _ => false,
+            },
+            None => false,
+        }
+    }
+}
+
+/// Whether a word starts an option, like `-vp`, rather than being a number like `-0.5`.
+fn is_option(word: &str) -> bool {
+    let mut chars = word.chars();
+    chars.next() == Some('-') && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+}
+
+fn words_to_string(option: &str, args: &[&str]) -> String {
+    let mut words = vec![option];
+    words.extend_from_slice(args);
+    words.join(" ")
+}
+
+/// Parse exactly `N` floats.
+fn parse_floats<const N: usize>(words: &[&str]) -> Option<[f32; N]> {
+    if words.len() != N {
+        return None;
+    }
+    let mut floats = [0.0; N];
+    for (float, word) in floats.iter_mut().zip(words) {
+        *float = word.parse().ok()?;
+    }
+    Some(floats)
+}
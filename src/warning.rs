@@ -0,0 +1,43 @@
+use std::fmt;
+
+/// A quirk in a Radiance HDR file that [`load_lenient`](super::load_lenient) recovered from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Warning {
+    /// The header set `FORMAT` more than once. The last value is used.
+    DuplicateFormat,
+    /// The header set a variable that is not part of the Radiance format.
+    UnknownVariable(String),
+    /// The header was not ended by an empty line before the resolution line.
+    MissingBlankLine,
+    /// The resolution line had to be cleaned up before it could be parsed, e.g. because of
+    /// lowercase axes or missing spaces.
+    SloppyResolutionLine,
+    /// The header had lines ending with CR LF or just CR instead of LF.
+    NonStandardLineEnding,
+    /// An `EXPOSURE` value was not a positive number, and was ignored.
+    InvalidExposure(String),
+    /// A `GAMMA` value was not a positive number, and was ignored.
+    InvalidGamma(String),
+    /// A `PIXASPECT` value was not a positive number, and was ignored.
+    InvalidPixelAspect(String),
+    /// A `COLORCORR` value was not three positive numbers, and was ignored.
+    InvalidColorCorrection(String),
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::DuplicateFormat => f.write_str("FORMAT is set more than once"),
+            Self::UnknownVariable(name) => write!(f, "unknown header variable {}", name),
+            Self::MissingBlankLine => f.write_str("no empty line before the resolution line"),
+            Self::SloppyResolutionLine => f.write_str("malformed resolution line"),
+            Self::NonStandardLineEnding => f.write_str("header lines end with CR"),
+            Self::InvalidExposure(value) => write!(f, "invalid exposure {}", value),
+            Self::InvalidGamma(value) => write!(f, "invalid gamma {}", value),
+            Self::InvalidPixelAspect(value) => write!(f, "invalid pixel aspect {}", value),
+            Self::InvalidColorCorrection(value) => {
+                write!(f, "invalid color correction {}", value)
+            }
+        }
+    }
+}
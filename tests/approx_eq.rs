@@ -0,0 +1,57 @@
+use radiant::{Image, RGB};
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+fn rgb(r: f32, g: f32, b: f32) -> RGB {
+    RGB { r, g, b }
+}
+
+#[test]
+fn approx_eq_absolute() {
+    let a = Image::new(2, 1, vec![rgb(1.0, 2.0, 3.0), rgb(0.0, 0.5, 100.0)]).unwrap();
+    let b = Image::new(2, 1, vec![rgb(1.05, 1.95, 3.0), rgb(0.0, 0.5, 100.1)]).unwrap();
+    assert!(a.approx_eq(&a, 0.0));
+    assert!(a.approx_eq(&b, 0.11));
+    assert!(b.approx_eq(&a, 0.11));
+    assert!(!a.approx_eq(&b, 0.06));
+
+    // The same pixels with other dimensions
+    let c = Image::new(1, 2, a.data.clone()).unwrap();
+    assert!(!a.approx_eq(&c, 1.0));
+}
+
+#[test]
+fn approx_eq_relative() {
+    let a = Image::new(2, 1, vec![rgb(1.0, 0.0, 1000.0), rgb(0.001, 1e30, 2.0)]).unwrap();
+    let b = Image::new(
+        2,
+        1,
+        vec![rgb(1.01, 0.0, 1010.0), rgb(0.00101, 1.01e30, 2.0)],
+    )
+    .unwrap();
+    assert!(a.approx_eq_relative(&b, 0.01));
+    assert!(!a.approx_eq_relative(&b, 0.005));
+    assert!(!a.approx_eq(&b, 1.0));
+}
+
+#[test]
+fn approx_eq_non_finite() {
+    let inf = Image::new(1, 1, vec![rgb(f32::INFINITY, 0.0, 0.0)]).unwrap();
+    assert!(inf.approx_eq(&inf, 0.0));
+    assert!(inf.approx_eq_relative(&inf, 0.0));
+
+    let nan = Image::new(1, 1, vec![rgb(f32::NAN, 0.0, 0.0)]).unwrap();
+    assert!(!nan.approx_eq(&nan, f32::INFINITY));
+    assert!(!nan.approx_eq_relative(&nan, 1.0));
+}
+
+#[test]
+fn approx_eq_round_trip() {
+    let image = radiant::load(HDR).unwrap();
+    let doubled = image.map(|pixel| pixel * 2.0);
+    let bytes = radiant::encode(&doubled).unwrap();
+    let loaded = radiant::load(&bytes[..]).unwrap();
+    // A shared exponent leaves 8 bits of precision relative to the brightest channel
+    assert!(loaded.approx_eq_relative(&doubled, 1.0 / 128.0));
+    assert!(!loaded.approx_eq(&image, 0.001));
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "bytemuck")]
+
+use radiant::RGB;
+
+#[test]
+fn bytemuck_cast() {
+    let pixels = [
+        RGB {
+            r: 1.0,
+            g: 2.0,
+            b: 3.0,
+        },
+        RGB {
+            r: 4.0,
+            g: 5.0,
+            b: 6.0,
+        },
+    ];
+    let floats: &[f32] = bytemuck::cast_slice(&pixels);
+    assert_eq!(floats, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(<RGB as bytemuck::Zeroable>::zeroed(), RGB::default());
+}
+
+#[test]
+fn as_f32_slice() {
+    let reader = b"#?RADIANCE\n\n-Y 1 +X 2\n\xff\x00\xff\x80\x00\xff\x00\x81";
+    let image = radiant::load(&reader[..]).unwrap();
+    assert_eq!(image.as_f32_slice(), [1.0, 0.0, 1.0, 0.0, 2.0, 0.0]);
+}
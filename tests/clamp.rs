@@ -0,0 +1,80 @@
+use radiant::{Image, RGB};
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+fn rgb(r: f32, g: f32, b: f32) -> RGB {
+    RGB { r, g, b }
+}
+
+#[test]
+fn clamp_channels() {
+    let mut image = Image::new(
+        2,
+        2,
+        vec![
+            rgb(-1.0, 0.5, 2.0),
+            rgb(f32::NAN, f32::INFINITY, f32::NEG_INFINITY),
+            rgb(0.0, 1.0, 0.25),
+            rgb(3.0, -0.0, 0.75),
+        ],
+    )
+    .unwrap();
+    image.clamp(0.0, 1.0);
+    assert_eq!(
+        image.data,
+        [
+            rgb(0.0, 0.5, 1.0),
+            rgb(0.0, 1.0, 0.0),
+            rgb(0.0, 1.0, 0.25),
+            rgb(1.0, -0.0, 0.75),
+        ]
+    );
+}
+
+#[test]
+#[should_panic]
+fn clamp_invalid_range() {
+    let mut image = Image::new(1, 1, vec![RGB::splat(1.0)]).unwrap();
+    image.clamp(1.0, 0.0);
+}
+
+#[test]
+fn normalize_brightest_channel() {
+    let mut image = radiant::load(HDR).unwrap();
+    image.normalize();
+    let max = image
+        .data
+        .iter()
+        .flat_map(|pixel| [pixel.r, pixel.g, pixel.b])
+        .fold(0.0, f32::max);
+    assert_eq!(max, 1.0);
+
+    let mut image = Image::new(
+        3,
+        1,
+        vec![
+            rgb(1.0, 4.0, 2.0),
+            rgb(f32::NAN, f32::INFINITY, 0.0),
+            rgb(0.5, 0.0, 8.0),
+        ],
+    )
+    .unwrap();
+    image.normalize();
+    assert_eq!(image.data[0], rgb(0.125, 0.5, 0.25));
+    assert!(image.data[1].r.is_nan());
+    assert_eq!(image.data[1].g, f32::INFINITY);
+    assert_eq!(image.data[2], rgb(0.0625, 0.0, 1.0));
+}
+
+#[test]
+fn normalize_black() {
+    let mut image = Image::new(2, 1, vec![RGB::splat(0.0), rgb(-1.0, f32::NAN, 0.0)]).unwrap();
+    image.normalize();
+    assert_eq!(image.data[0], RGB::splat(0.0));
+    assert_eq!(image.data[1].r, -1.0);
+    assert!(image.data[1].g.is_nan());
+
+    let mut empty = Image::new(0, 0, Vec::new()).unwrap();
+    empty.normalize();
+    assert!(empty.is_empty());
+}
@@ -0,0 +1,143 @@
+use radiant::{ColorFormat, ColorMatrix, Header, Primaries, RGB};
+
+fn assert_close(a: [f32; 3], b: [f32; 3]) {
+    for (a, b) in a.iter().zip(&b) {
+        assert!((a - b).abs() < 1e-5, "{:?} != {:?}", a, b);
+    }
+}
+
+#[test]
+fn rgb_to_xyz_radiance_luminance() {
+    // The Y row matches the luminance weights Radiance uses for its default primaries
+    let matrix = Primaries::default().rgb_to_xyz();
+    assert_close(matrix[1], [0.265_074_13, 0.670_114_6, 0.064_811_24]);
+}
+
+#[test]
+fn rgb_to_xyz_white() {
+    let white = RGB {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+    };
+    // The default white point is 0.3333 rather than exactly a third
+    assert_close(white.to_xyz(), [1.0, 1.0, 0.3334 / 0.3333]);
+}
+
+#[test]
+fn xyz_round_trip() {
+    let pixel = RGB {
+        r: 0.2,
+        g: 1.5,
+        b: 7.0,
+    };
+    let back = RGB::from_xyz(pixel.to_xyz());
+    assert_close([back.r, back.g, back.b], [pixel.r, pixel.g, pixel.b]);
+}
+
+#[test]
+fn rgb_to_xyz_from_header_primaries() {
+    let reader = b"#?RADIANCE\n\
+        PRIMARIES= 0.640 0.330 0.300 0.600 0.150 0.060 0.3127 0.3290\n\
+        \n-Y 1 +X 1\n\xff\x00\xff\x80";
+    let (_, header) = radiant::load_with_header(&reader[..]).unwrap();
+    let primaries = header.primaries.unwrap();
+    assert_eq!(primaries.white, [0.3127, 0.3290]);
+
+    // These are the sRGB primaries, which have a well known matrix
+    let matrix = header.rgb_to_xyz();
+    let expected = [
+        [0.412_456_4, 0.357_576_1, 0.180_437_5],
+        [0.212_672_9, 0.715_152_2, 0.072_175],
+        [0.019_333_9, 0.119_192, 0.950_304_1],
+    ];
+    for (row, expected) in matrix.iter().zip(&expected) {
+        for (a, b) in row.iter().zip(expected) {
+            assert!((a - b).abs() < 1e-3, "{:?} != {:?}", matrix, expected);
+        }
+    }
+}
+
+fn assert_matrix_close(matrix: ColorMatrix, expected: ColorMatrix) {
+    for (row, expected) in matrix.iter().zip(&expected) {
+        assert_close(*row, *expected);
+    }
+}
+
+#[test]
+fn rgb_to_xyz_radiance_matrix() {
+    assert_matrix_close(
+        Primaries::default().rgb_to_xyz(),
+        [
+            [0.514_083_15, 0.323_888_74, 0.162_028_1],
+            [0.265_074_13, 0.670_114_6, 0.064_811_24],
+            [0.024_097_648, 0.122_854_35, 0.853_348],
+        ],
+    );
+}
+
+#[test]
+fn conversion_radiance_to_rec709() {
+    // Includes the Bradford adaptation from the Radiance white point to D65
+    assert_matrix_close(
+        Primaries::default().conversion_to(&Primaries::REC709),
+        [
+            [1.164_398_1, -0.156_482_03, -0.007_916_047],
+            [0.007_593_666, 0.993_014_3, -0.000_607_995_2],
+            [0.003_543_360_3, 0.018_184_977, 0.978_271_66],
+        ],
+    );
+}
+
+#[test]
+fn conversion_same_primaries() {
+    for primaries in [Primaries::default(), Primaries::REC709] {
+        assert_matrix_close(
+            primaries.conversion_to(&primaries),
+            [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        );
+    }
+}
+
+#[test]
+fn convert_primaries_image() {
+    let reader = b"#?RADIANCE\n\n-Y 1 +X 2\n\x80\x80\x80\x81\x80\x40\xff\x81";
+    let (mut image, header) = radiant::load_with_header(&reader[..]).unwrap();
+    let original = image.data.clone();
+    image.convert_primaries(&header, &Primaries::REC709);
+
+    // White stays white
+    let white = image.data[0];
+    assert_close([white.r, white.g, white.b], [256.0 / 255.0; 3]);
+
+    let matrix = Primaries::default().conversion_to(&Primaries::REC709);
+    let converted = image.data[1];
+    assert_eq!(
+        [converted.r, converted.g, converted.b],
+        original[1].transform(&matrix)
+    );
+
+    // Converting to the primaries of the file does nothing
+    let header = Header {
+        primaries: Some(Primaries::REC709),
+        ..Header::default()
+    };
+    let before = image.data.clone();
+    image.convert_primaries(&header, &Primaries::REC709);
+    for (a, b) in image.data.iter().zip(&before) {
+        assert_close([a.r, a.g, a.b], [b.r, b.g, b.b]);
+    }
+}
+
+#[test]
+fn convert_primaries_xyze() {
+    let reader = b"#?RADIANCE\nFORMAT=32-bit_rle_xyze\n\n-Y 1 +X 1\n\x80\x80\x80\x81";
+    let (mut image, header) = radiant::load_with_header(&reader[..]).unwrap();
+    assert_eq!(image.color_format, ColorFormat::Xyze);
+    image.convert_primaries(&header, &Primaries::REC709);
+    assert_eq!(image.color_format, ColorFormat::Rgbe);
+
+    // Equal energy white in XYZ becomes white in Rec. 709
+    let white = image.data[0];
+    assert_close([white.r, white.g, white.b], [256.0 / 255.0; 3]);
+}
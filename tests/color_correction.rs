@@ -0,0 +1,76 @@
+use radiant::{LoadError, Warning, RGB};
+
+/// One pixel with two color corrections, making red twice and blue half as bright as captured.
+const TWO_CORRECTIONS: &[u8] = b"#?RADIANCE\n\
+    COLORCORR= 4 1 0.25\n\
+    FORMAT=32-bit_rle_rgbe\n\
+    COLORCORR=0.5 1 2\n\
+    \n-Y 1 +X 1\n\x80\x40\xff\x81";
+
+fn with_color_correction(value: &str) -> Vec<u8> {
+    let mut bytes = format!("#?RADIANCE\nCOLORCORR={}\n\n-Y 1 +X 1\n", value).into_bytes();
+    bytes.extend_from_slice(b"\x80\x40\xff\x81");
+    bytes
+}
+
+#[test]
+fn color_correction_product() {
+    let (mut image, header) = radiant::load_with_header(TWO_CORRECTIONS).unwrap();
+    assert_eq!(header.color_correction, Some([2.0, 1.0, 0.5]));
+
+    image.undo_color_correction(&header);
+    assert_eq!(
+        image.data[0],
+        RGB {
+            r: 128.0 / 255.0 * 2.0 / 2.0,
+            g: 64.0 / 255.0 * 2.0,
+            b: 255.0 / 255.0 * 2.0 / 0.5,
+        }
+    );
+}
+
+#[test]
+fn color_correction_missing() {
+    let bytes = with_color_correction("1 1 1");
+    let (mut image, mut header) = radiant::load_with_header(&bytes[..]).unwrap();
+    header.color_correction = None;
+    let stored = image.data.clone();
+    image.undo_color_correction(&header);
+    assert_eq!(image.data, stored);
+}
+
+#[test]
+fn color_correction_invalid_values() {
+    for value in ["1 1", "1 1 1 1", "1 green 1", "0 1 1", "1 -1 1", "1 1 inf"] {
+        let bytes = with_color_correction(value);
+
+        let (_, header) = radiant::load_with_header(&bytes[..]).unwrap();
+        assert_eq!(header.color_correction, None, "{}", value);
+
+        let (_, warnings) = radiant::load_lenient(&bytes[..]).unwrap();
+        assert_eq!(
+            warnings,
+            [Warning::InvalidColorCorrection(value.to_owned())]
+        );
+
+        assert!(matches!(
+            radiant::load_strict(&bytes[..]),
+            Err(LoadError::FileFormat)
+        ));
+    }
+    assert!(radiant::load_strict(TWO_CORRECTIONS).is_ok());
+}
+
+#[test]
+fn color_correction_round_trip() {
+    let (image, header) = radiant::load_with_header(TWO_CORRECTIONS).unwrap();
+    let mut buf = Vec::new();
+    radiant::save_with_header(&image, &header, &mut buf).unwrap();
+    assert!(buf.starts_with(
+        b"#?RADIANCE\nCOLORCORR= 4 1 0.25\nCOLORCORR=0.5 1 2\nFORMAT=32-bit_rle_rgbe\n\n-Y 1 +X 1\n"
+    ));
+
+    let (loaded, loaded_header) = radiant::load_with_header(&buf[..]).unwrap();
+    assert_eq!(loaded.data, image.data);
+    assert_eq!(loaded_header.color_correction, header.color_correction);
+}
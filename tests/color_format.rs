@@ -0,0 +1,77 @@
+use radiant::{ColorFormat, LoadError, PushDecoder};
+
+fn with_format(value: &str) -> Vec<u8> {
+    let mut bytes = format!("#?RADIANCE\nFORMAT={}\n\n-Y 1 +X 1\n", value).into_bytes();
+    bytes.extend_from_slice(b"\xff\x00\xff\x80");
+    bytes
+}
+
+const XYZE: &[u8] = b"#?RADIANCE\nFORMAT=32-bit_rle_xyze\n\n-Y 1 +X 2\n\
+    \xff\x80\x00\x80\x00\xff\x00\x81";
+
+#[test]
+fn color_format_xyze() {
+    let (image, header) = radiant::load_with_header(XYZE).unwrap();
+    assert_eq!(header.format, Some(ColorFormat::Xyze));
+    assert_eq!(image.color_format, ColorFormat::Xyze);
+    assert_eq!(image.data[0].r, 1.0);
+    assert_eq!(image.data[1].g, 2.0);
+}
+
+#[test]
+fn color_format_defaults_to_rgbe() {
+    let reader = b"#?RADIANCE\n\n-Y 1 +X 1\n\xff\x00\xff\x80";
+    let (image, header) = radiant::load_with_header(&reader[..]).unwrap();
+    assert_eq!(header.format, None);
+    assert_eq!(image.color_format, ColorFormat::Rgbe);
+}
+
+#[test]
+fn color_format_push_decoder() {
+    let mut decoder = PushDecoder::new();
+    for chunk in XYZE.chunks(5) {
+        decoder.feed(chunk).unwrap();
+    }
+    assert_eq!(decoder.finish().unwrap().color_format, ColorFormat::Xyze);
+}
+
+#[test]
+fn color_format_saved() {
+    let image = radiant::load(XYZE).unwrap();
+    let bytes = radiant::encode(&image).unwrap();
+    let loaded = radiant::load(&bytes[..]).unwrap();
+    assert_eq!(loaded.color_format, ColorFormat::Xyze);
+    assert_eq!(loaded.data, image.data);
+}
+
+#[test]
+fn color_format_rgbe() {
+    let bytes = with_format("32-bit_rle_rgbe");
+    let (image, header) = radiant::load_with_header(&bytes[..]).unwrap();
+    assert_eq!(header.format, Some(ColorFormat::Rgbe));
+    assert_eq!(image.color_format, ColorFormat::Rgbe);
+}
+
+#[test]
+fn color_format_unsupported() {
+    let bytes = with_format(" 16-bit_rle_rgbe ");
+    let unsupported = |result: Result<_, LoadError>| matches!(result, Err(LoadError::UnsupportedFormat(format)) if format == "16-bit_rle_rgbe");
+    assert!(unsupported(radiant::load(&bytes[..]).map(|_| ())));
+    assert!(unsupported(radiant::read_header(&bytes[..]).map(|_| ())));
+    assert!(unsupported(radiant::load_lenient(&bytes[..]).map(|_| ())));
+    assert!(unsupported(PushDecoder::new().feed(&bytes).map(|_| ())));
+
+    // Only the last value counts
+    let mut bytes = b"#?RADIANCE\nFORMAT=bogus\n".to_vec();
+    bytes.extend_from_slice(&with_format("32-bit_rle_rgbe")[11..]);
+    assert!(radiant::load(&bytes[..]).is_ok());
+}
+
+#[test]
+fn color_format_empty() {
+    let bytes = with_format("");
+    assert!(matches!(
+        radiant::load(&bytes[..]),
+        Err(LoadError::FileFormat)
+    ));
+}
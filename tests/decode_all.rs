@@ -0,0 +1,31 @@
+use radiant::LoadError;
+
+const FIRST: &[u8] = b"#?RADIANCE\0\n\n-Y 1 +X 8\n\
+    \x02\x02\x00\x08\
+    \x88\xff\x88\x00\x88\xff\x88\x80";
+
+const SECOND: &[u8] = b"#?RADIANCE\n\n-Y 2 +X 1\n\xff\x00\xff\x80\x00\xff\x00\x80";
+
+#[test]
+fn decode_all_until_eof() {
+    let bytes = [FIRST, SECOND, FIRST].concat();
+    let images = radiant::decode_all(&bytes[..])
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let dimensions: Vec<_> = images.iter().map(|i| (i.width, i.height)).collect();
+    assert_eq!(dimensions, [(8, 1), (1, 2), (8, 1)]);
+}
+
+#[test]
+fn decode_all_empty() {
+    assert_eq!(radiant::decode_all(&b""[..]).count(), 0);
+}
+
+#[test]
+fn decode_all_truncated() {
+    let bytes = [FIRST, &SECOND[..SECOND.len() - 1]].concat();
+    let mut images = radiant::decode_all(&bytes[..]);
+    assert!(images.next().unwrap().is_ok());
+    assert!(matches!(images.next(), Some(Err(LoadError::Eof(_)))));
+    assert!(images.next().is_none());
+}
@@ -0,0 +1,85 @@
+use radiant::DecodeContext;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Counts the allocations made by each thread, so that tests running in parallel don't see each
+/// other's.
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAlloc = CountingAlloc;
+
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+/// A 64x64 image with the adaptive run-length encoding.
+fn tile() -> Vec<u8> {
+    let mut bytes = b"#?RADIANCE\n\n-Y 64 +X 64\n".to_vec();
+    for y in 0..64u8 {
+        bytes.extend_from_slice(&[2, 2, 0, 64]);
+        for value in [y, 0, 64 - y, 0x80] {
+            bytes.extend_from_slice(&[0x80 | 64, value]);
+        }
+    }
+    bytes
+}
+
+#[test]
+fn decode_context_matches_load() {
+    let bytes = tile();
+    let expected = radiant::load(&bytes[..]).unwrap();
+
+    let mut context = DecodeContext::new();
+    assert_eq!(context.load(&bytes[..]).unwrap().data, expected.data);
+
+    let mut data = Vec::new();
+    assert_eq!(context.load_into(&bytes[..], &mut data).unwrap(), (64, 64));
+    assert_eq!(data, expected.data);
+}
+
+#[test]
+fn decode_context_allocates_nothing() {
+    let bytes = tile();
+    let mut context = DecodeContext::new();
+    let mut data = Vec::new();
+    context.load_into(&bytes[..], &mut data).unwrap();
+
+    let reused = allocations(|| {
+        for _ in 0..100 {
+            context.load_into(&bytes[..], &mut data).unwrap();
+        }
+    });
+    assert_eq!(reused, 0);
+
+    // Only the pixels of each new image
+    let loaded = allocations(|| {
+        for _ in 0..100 {
+            context.load(&bytes[..]).unwrap();
+        }
+    });
+    assert_eq!(loaded, 100);
+}
+
+#[test]
+fn decode_context_is_send() {
+    fn assert_send<T: Send>() {}
+    assert_send::<DecodeContext>();
+}
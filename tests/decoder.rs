@@ -0,0 +1,132 @@
+use radiant::{Decoder, LoadError, RGB};
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+#[test]
+fn decoder_matches_load() {
+    let full = radiant::load(HDR).unwrap();
+    let mut decoder = Decoder::new(HDR).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (16, 4));
+    assert_eq!(decoder.scanline_len(), 16);
+
+    let mut row = vec![RGB::default(); 16];
+    for (y, expected) in full.rows().enumerate() {
+        assert_eq!(decoder.rows_remaining(), 4 - y);
+        decoder.read_scanline(&mut row).unwrap();
+        assert_eq!(row, expected);
+    }
+    assert_eq!(decoder.rows_remaining(), 0);
+
+    let progress = decoder.progress();
+    assert_eq!((progress.rows_done, progress.rows_total), (4, 4));
+    assert_eq!(progress.bytes_read, HDR.len() as u64);
+}
+
+#[test]
+fn decoder_next_row() {
+    let full = radiant::load(HDR).unwrap();
+    let mut decoder = Decoder::new(HDR).unwrap();
+    let mut row = vec![RGB::default(); 16];
+    let mut rows = Vec::new();
+    while decoder.next_row(&mut row).unwrap() {
+        rows.extend_from_slice(&row);
+    }
+    assert_eq!(rows, full.data);
+    assert!(!decoder.next_row(&mut row).unwrap());
+}
+
+#[test]
+fn decoder_wrong_buffer_size() {
+    let mut decoder = Decoder::new(HDR).unwrap();
+    let mut row = vec![RGB::default(); 15];
+    assert!(matches!(
+        decoder.read_scanline(&mut row),
+        Err(LoadError::BufferSize {
+            expected: 16,
+            actual: 15
+        })
+    ));
+    assert_eq!(decoder.rows_remaining(), 4);
+}
+
+#[test]
+fn decoder_past_end() {
+    let mut decoder = Decoder::new(HDR).unwrap();
+    let mut row = vec![RGB::default(); 16];
+    for _ in 0..4 {
+        decoder.read_scanline(&mut row).unwrap();
+    }
+    assert!(matches!(
+        decoder.read_scanline(&mut row),
+        Err(LoadError::PastEnd)
+    ));
+}
+
+#[test]
+fn decoder_empty_image() {
+    let mut decoder = Decoder::new(&b"#?RADIANCE\n\n-Y 0 +X 3\n"[..]).unwrap();
+    assert_eq!(decoder.rows_remaining(), 0);
+    assert!(matches!(
+        decoder.read_scanline(&mut []),
+        Err(LoadError::PastEnd)
+    ));
+}
+
+#[test]
+fn decoder_skip_scanlines() {
+    let full = radiant::load(HDR).unwrap();
+    let mut decoder = Decoder::new(HDR).unwrap();
+    let mut row = vec![RGB::default(); 16];
+
+    decoder.skip_scanlines(2).unwrap();
+    assert_eq!(decoder.rows_remaining(), 2);
+    decoder.read_scanline(&mut row).unwrap();
+    assert_eq!(row, full.rows().nth(2).unwrap());
+
+    assert!(matches!(decoder.skip_scanlines(2), Err(LoadError::PastEnd)));
+    assert_eq!(decoder.rows_remaining(), 1);
+    decoder.skip_scanlines(1).unwrap();
+    assert_eq!(decoder.progress().bytes_read, HDR.len() as u64);
+}
+
+#[test]
+fn decoder_skip_run_length_encoded() {
+    let image = radiant::Image::new(
+        40,
+        6,
+        (0..240).map(|i| RGB::splat((i / 7) as f32)).collect(),
+    )
+    .unwrap();
+    let mut bytes = Vec::new();
+    radiant::save_rle(&image, &mut bytes).unwrap();
+    // Old-style runs as well
+    bytes.extend_from_slice(b"#?RADIANCE\n\n-Y 3 +X 300\n");
+    for _ in 0..3 {
+        bytes.extend_from_slice(&[0x80, 0, 0, 0x80, 1, 1, 1, 43, 1, 1, 1, 1]);
+    }
+    let full = radiant::load(&bytes[..]).unwrap();
+
+    let mut reader = &bytes[..];
+    let mut decoder = Decoder::new(&mut reader).unwrap();
+    let mut row = vec![RGB::default(); 40];
+    for y in 0..6 {
+        if y % 2 == 0 {
+            decoder.skip_scanlines(1).unwrap();
+        } else {
+            decoder.read_scanline(&mut row).unwrap();
+            assert_eq!(row, full.rows().nth(y).unwrap());
+        }
+    }
+    drop(decoder);
+
+    let mut decoder = Decoder::new(&mut reader).unwrap();
+    decoder.skip_scanlines(3).unwrap();
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn decoder_skip_bad_rle() {
+    let bytes = b"#?RADIANCE\n\n-Y 1 +X 8\n\x02\x02\x00\x08\x89\x00";
+    let mut decoder = Decoder::new(&bytes[..]).unwrap();
+    assert!(matches!(decoder.skip_scanlines(1), Err(LoadError::Rle)));
+}
@@ -0,0 +1,47 @@
+use radiant::{Image, RGB};
+use std::num::NonZeroUsize;
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+fn factor(n: usize) -> NonZeroUsize {
+    NonZeroUsize::new(n).unwrap()
+}
+
+#[test]
+fn downsample_box_partial_blocks() {
+    // 5x3 pixels, each holding its index
+    let image = Image::new(5, 3, (0..15).map(|i| RGB::splat(i as f32)).collect()).unwrap();
+    let small = image.downsample_box(factor(2));
+    assert_eq!((small.width, small.height), (3, 2));
+    let values: Vec<f32> = small.data.iter().map(|pixel| pixel.r).collect();
+    assert_eq!(values, [3.0, 5.0, 6.5, 10.5, 12.5, 14.0]);
+}
+
+#[test]
+fn downsample_box_matches_thumbnail() {
+    let image = radiant::load(HDR).unwrap();
+    for n in [1, 2, 3, 4, 8, 16, 20] {
+        let small = image.downsample_box(factor(n));
+        let longest = image.width.max(image.height);
+        let thumbnail = radiant::load_thumbnail(HDR, longest.div_ceil(n)).unwrap();
+        assert_eq!(
+            (small.width, small.height),
+            (thumbnail.width, thumbnail.height)
+        );
+        for (a, b) in small.data.iter().zip(&thumbnail.data) {
+            assert!(
+                (a.r - b.r).abs() < 1e-5 && (a.b - b.b).abs() < 1e-5,
+                "factor {}",
+                n
+            );
+        }
+    }
+}
+
+#[test]
+fn downsample_box_factor_one() {
+    let image = radiant::load(HDR).unwrap();
+    let same = image.downsample_box(factor(1));
+    assert_eq!((same.width, same.height), (image.width, image.height));
+    assert_eq!(same.data, image.data);
+}
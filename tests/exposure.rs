@@ -0,0 +1,34 @@
+//! Scanlines with the adaptive run-length encoding apply the exponent to a whole scanline at once
+//! with the `simd` feature, so check that against flat pixels, which are converted one by one.
+
+const HEADER: &[u8] = b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y 1 +X 256\n";
+
+fn pixel(i: u8) -> [u8; 4] {
+    [200, i, 255 - i, i]
+}
+
+#[test]
+fn every_exponent_matches_flat() {
+    let mut flat = HEADER.to_vec();
+    for i in 0..=255 {
+        flat.extend_from_slice(&pixel(i));
+    }
+
+    let mut rle = HEADER.to_vec();
+    rle.extend_from_slice(&[2, 2, 1, 0]);
+    for channel in 0..4 {
+        for half in [0..128, 128..256] {
+            rle.push(128);
+            rle.extend(half.map(|i| pixel(i as u8)[channel]));
+        }
+    }
+
+    let flat = radiant::load(&flat[..]).unwrap();
+    let rle = radiant::load(&rle[..]).unwrap();
+    assert_eq!(rle.width, 256);
+    for (a, b) in flat.data.iter().zip(&rle.data) {
+        assert_eq!(a.r.to_bits(), b.r.to_bits());
+        assert_eq!(a.g.to_bits(), b.g.to_bits());
+        assert_eq!(a.b.to_bits(), b.b.to_bits());
+    }
+}
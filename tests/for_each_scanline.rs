@@ -0,0 +1,40 @@
+use std::ops::ControlFlow;
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+#[test]
+fn for_each_scanline_matches_load() {
+    let mut data = Vec::new();
+    let mut indices = Vec::new();
+    radiant::for_each_scanline(HDR, |index, scanline| {
+        assert_eq!(scanline.len(), 16);
+        indices.push(index);
+        data.extend_from_slice(scanline);
+        ControlFlow::Continue(())
+    })
+    .unwrap();
+    assert_eq!(indices, [0, 1, 2, 3]);
+    assert_eq!(data, radiant::load(HDR).unwrap().data);
+}
+
+#[test]
+fn for_each_scanline_break() {
+    let mut calls = 0;
+    radiant::for_each_scanline(HDR, |index, _| {
+        calls += 1;
+        if index == 1 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+    .unwrap();
+    assert_eq!(calls, 2);
+}
+
+#[test]
+fn for_each_scanline_error() {
+    let result =
+        radiant::for_each_scanline(&HDR[..HDR.len() - 1], |_, _| ControlFlow::Continue(()));
+    assert!(matches!(result, Err(radiant::LoadError::Eof(_))));
+}
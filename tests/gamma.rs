@@ -0,0 +1,87 @@
+use radiant::{Header, Image, LoadError, Warning, RGB};
+
+fn with_gamma(value: &str) -> Vec<u8> {
+    let mut bytes = format!("#?RADIANCE\nGAMMA={}\n\n-Y 1 +X 1\n", value).into_bytes();
+    bytes.extend_from_slice(b"\x80\x40\xff\x81");
+    bytes
+}
+
+#[test]
+fn gamma_round_trip() {
+    let gamma = 2.2;
+    let linear: Vec<RGB> = (1..=64)
+        .map(|i| RGB {
+            r: i as f32 / 16.0,
+            g: 1.0 / i as f32,
+            b: 0.5,
+        })
+        .collect();
+
+    // Write the pixels gamma encoded, saying so in the header
+    let encoded = Image::new(
+        8,
+        8,
+        linear
+            .iter()
+            .map(|p| RGB {
+                r: p.r.powf(1.0 / gamma),
+                g: p.g.powf(1.0 / gamma),
+                b: p.b.powf(1.0 / gamma),
+            })
+            .collect(),
+    )
+    .unwrap();
+    let header = Header {
+        raw: vec![format!("GAMMA={}", gamma)],
+        ..Header::default()
+    };
+    let mut bytes = Vec::new();
+    radiant::save_with_header(&encoded, &header, &mut bytes).unwrap();
+
+    let (mut image, header) = radiant::load_with_header(&bytes[..]).unwrap();
+    assert_eq!(header.gamma, Some(gamma));
+    image.apply_gamma_decode(header.gamma.unwrap());
+    for (a, b) in image.data.iter().zip(&linear) {
+        // A shared exponent leaves 8 bits of precision relative to the brightest channel, and
+        // decoding the gamma magnifies the error
+        let tolerance = b.r.max(b.g).max(b.b) * 0.02;
+        for (a, b) in [(a.r, b.r), (a.g, b.g), (a.b, b.b)] {
+            assert!((a - b).abs() <= tolerance, "{:?} != {:?}", a, b);
+        }
+    }
+}
+
+#[test]
+fn gamma_one_is_identity() {
+    let bytes = with_gamma("1");
+    let mut image = radiant::load(&bytes[..]).unwrap();
+    let stored = image.data.clone();
+    image.apply_gamma_decode(1.0);
+    assert_eq!(image.data, stored);
+}
+
+#[test]
+#[should_panic]
+fn gamma_zero_panics() {
+    let mut image = radiant::load(&with_gamma("1")[..]).unwrap();
+    image.apply_gamma_decode(0.0);
+}
+
+#[test]
+fn gamma_invalid_values() {
+    for value in ["0", "-2.2", "dark", "NaN"] {
+        let bytes = with_gamma(value);
+
+        let (_, header) = radiant::load_with_header(&bytes[..]).unwrap();
+        assert_eq!(header.gamma, None, "{}", value);
+
+        let (_, warnings) = radiant::load_lenient(&bytes[..]).unwrap();
+        assert_eq!(warnings, [Warning::InvalidGamma(value.to_owned())]);
+
+        assert!(matches!(
+            radiant::load_strict(&bytes[..]),
+            Err(LoadError::FileFormat)
+        ));
+    }
+    assert!(radiant::load_strict(&with_gamma("2.2")[..]).is_ok());
+}
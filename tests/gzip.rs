@@ -0,0 +1,42 @@
+use radiant::LoadError;
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+const HDR_GZ: &[u8] = include_bytes!("../assets/gradient.hdr.gz");
+
+#[test]
+fn gzip_rejected_by_load() {
+    assert!(matches!(radiant::load(HDR_GZ), Err(LoadError::Gzip)));
+    radiant::load(HDR).unwrap();
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gzip_load_gz() {
+    let expected = radiant::load(HDR).unwrap();
+    let image = radiant::load_gz(HDR_GZ).unwrap();
+    assert_eq!(
+        (image.width, image.height),
+        (expected.width, expected.height)
+    );
+    assert_eq!(image.data, expected.data);
+
+    let image = radiant::load_gz(HDR).unwrap();
+    assert_eq!(image.data, expected.data);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gzip_load_path() {
+    let expected = radiant::load(HDR).unwrap();
+    let image = radiant::load_path("assets/gradient.hdr.gz").unwrap();
+    assert_eq!(image.data, expected.data);
+}
+
+#[cfg(not(feature = "gzip"))]
+#[test]
+fn gzip_load_path_without_feature() {
+    assert!(matches!(
+        radiant::load_path("assets/gradient.hdr.gz"),
+        Err(LoadError::Gzip)
+    ));
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "half")]
+
+use half::f16;
+use radiant::F16Options;
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+/// A 1x1 image far brighter than f16 can hold.
+const BRIGHT: &[u8] = b"#?RADIANCE\n\n-Y 1 +X 1\n\xff\x80\x40\xa0";
+
+fn assert_close(half: f16, full: f32) {
+    let error = (half.to_f32() - full).abs();
+    assert!(error <= full.abs() * 1e-3, "{} vs {}", half, full);
+}
+
+#[test]
+fn load_f16_rgb_matches_load() {
+    let (width, height, data) = radiant::load_f16(HDR, F16Options::default()).unwrap();
+    assert_eq!((width, height), (16, 4));
+    assert_eq!(data.len(), 16 * 4 * 3);
+
+    let image = radiant::load(HDR).unwrap();
+    for (rgb, pixel) in data.chunks_exact(3).zip(&image.data) {
+        assert_close(rgb[0], pixel.r);
+        assert_close(rgb[1], pixel.g);
+        assert_close(rgb[2], pixel.b);
+    }
+}
+
+#[test]
+fn load_f16_rounds_like_from_f32() {
+    let (_, _, data) = radiant::load_f16(HDR, F16Options::default()).unwrap();
+    let image = radiant::load(HDR).unwrap();
+    let expected: Vec<f16> = image
+        .data
+        .iter()
+        .flat_map(|pixel| [pixel.r, pixel.g, pixel.b])
+        .map(f16::from_f32)
+        .collect();
+    assert_eq!(data, expected);
+}
+
+#[test]
+fn load_f16_rgba_matches_to_f16() {
+    let options = F16Options {
+        alpha: Some(f16::ONE),
+        ..Default::default()
+    };
+    let (_, _, data) = radiant::load_f16(HDR, options).unwrap();
+    assert_eq!(data.len(), 16 * 4 * 4);
+    assert!(data.chunks_exact(4).all(|rgba| rgba[3] == f16::ONE));
+    assert_eq!(data, radiant::load(HDR).unwrap().to_f16(options));
+}
+
+#[test]
+fn load_f16_clamps_by_default() {
+    let (_, _, data) = radiant::load_f16(BRIGHT, F16Options::default()).unwrap();
+    assert_eq!(data, [f16::MAX; 3]);
+}
+
+#[test]
+fn load_f16_allow_infinity() {
+    let options = F16Options {
+        allow_infinity: true,
+        ..Default::default()
+    };
+    let (_, _, data) = radiant::load_f16(BRIGHT, options).unwrap();
+    assert_eq!(data, [f16::INFINITY; 3]);
+}
@@ -0,0 +1,187 @@
+use radiant::{ColorFormat, Header, HeaderLine, Primaries, ViewType};
+
+#[test]
+fn header_variables() {
+    let reader = b"#?RADIANCE\n\
+        # made by hand\n\
+        SOFTWARE= radiant tests 1.0\n\
+        FORMAT=32-bit_rle_rgbe\n\
+        EXPOSURE=2\n\
+        GAMMA=2.2\n\
+        COLORCORR= 1 0.5 2\n\
+        EXPOSURE= 0.25\n\
+        COLORCORR=2 2 2\n\
+        \n-Y 1 +X 1\n\xff\x00\xff\x80";
+    let (image, header) = radiant::load_with_header(&reader[..]).unwrap();
+    assert_eq!(image.data, radiant::load(&reader[..]).unwrap().data);
+
+    assert_eq!(header.exposure, Some(0.5));
+    assert_eq!(header.gamma, Some(2.2));
+    assert_eq!(header.color_correction, Some([2.0, 1.0, 4.0]));
+    assert_eq!(header.software.as_deref(), Some("radiant tests 1.0"));
+    assert_eq!(header.raw.len(), 8);
+    assert_eq!(header.raw[0], "# made by hand");
+    assert_eq!(header.raw[7], "COLORCORR=2 2 2");
+}
+
+#[test]
+fn header_every_field() {
+    let reader = b"#?RADIANCE\n\
+        # made by hand\n\
+        pfilt -x 512 -y 512 in.hdr\n\
+        SOFTWARE=radiant tests\n\
+        FORMAT=32-bit_rle_xyze\n\
+        EXPOSURE=4\n\
+        GAMMA=1.8\n\
+        COLORCORR=1 2 3\n\
+        PRIMARIES=0.64 0.33 0.3 0.6 0.15 0.06 0.3127 0.329\n\
+        PIXASPECT=2\n\
+        VIEW= -vtv -vp 0 0 0\n\
+        MADE_UP=one=two \n\
+        PIXASPECT=0.25\n\
+        VIEW=-vth\n\
+        \n-Y 1 +X 1\n\xff\x00\xff\x80";
+    let (_, header) = radiant::load_with_header(&reader[..]).unwrap();
+
+    assert_eq!(header.format, Some(ColorFormat::Xyze));
+    assert_eq!(header.exposure, Some(4.0));
+    assert_eq!(header.gamma, Some(1.8));
+    assert_eq!(header.color_correction, Some([1.0, 2.0, 3.0]));
+    assert_eq!(
+        header.primaries,
+        Some(Primaries {
+            red: [0.64, 0.33],
+            green: [0.3, 0.6],
+            blue: [0.15, 0.06],
+            white: [0.3127, 0.329],
+        })
+    );
+    assert_eq!(header.software.as_deref(), Some("radiant tests"));
+    assert_eq!(header.pixel_aspect, Some(0.5));
+    assert_eq!(header.view.unwrap().view_type, ViewType::Hemispherical);
+    assert_eq!(
+        header.other_variables,
+        [("MADE_UP".to_owned(), "one=two ".to_owned())]
+    );
+    assert_eq!(
+        header.comments,
+        ["# made by hand", "pfilt -x 512 -y 512 in.hdr"]
+    );
+    assert_eq!(header.raw.len(), 13);
+}
+
+#[test]
+fn header_empty() {
+    let reader = b"#?RADIANCE\n\n-Y 1 +X 1\n\xff\x00\xff\x80";
+    let (_, header) = radiant::load_with_header(&reader[..]).unwrap();
+    assert_eq!(
+        header,
+        Header {
+            program: Some("RADIANCE".to_owned()),
+            ..Header::default()
+        }
+    );
+}
+
+#[test]
+fn header_invalid_values_ignored() {
+    let reader = b"#?RADIANCE\nEXPOSURE=bright\nCOLORCORR=1 2\n\n-Y 1 +X 1\n\xff\x00\xff\x80";
+    let (_, header) = radiant::load_with_header(&reader[..]).unwrap();
+    assert_eq!(header.exposure, None);
+    assert_eq!(header.color_correction, None);
+    assert_eq!(header.raw.len(), 2);
+}
+
+#[test]
+fn header_lines() {
+    let reader = b"#?RADIANCE\n\
+        # made by hand\n\
+        SOFTWARE= radiant tests \n\
+        CAPDATE=2024:01:02 03:04:05\n\
+        pfilt -x 512 -y 512 in.hdr\n\
+        # A=1\n\
+        FORMAT=32-bit_rle_rgbe\n\
+        \n-Y 1 +X 1\n\xff\x00\xff\x80";
+    let (_, header) = radiant::load_with_header(&reader[..]).unwrap();
+
+    let lines: Vec<_> = header.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            HeaderLine::Comment("# made by hand"),
+            HeaderLine::Variable {
+                name: "SOFTWARE",
+                value: " radiant tests ",
+            },
+            HeaderLine::Variable {
+                name: "CAPDATE",
+                value: "2024:01:02 03:04:05",
+            },
+            HeaderLine::Comment("pfilt -x 512 -y 512 in.hdr"),
+            HeaderLine::Comment("# A=1"),
+            HeaderLine::Variable {
+                name: "FORMAT",
+                value: "32-bit_rle_rgbe",
+            },
+        ]
+    );
+
+    assert_eq!(header.get("SOFTWARE"), Some("radiant tests"));
+    assert_eq!(header.get("CAPDATE"), Some("2024:01:02 03:04:05"));
+    assert_eq!(header.get("A"), None);
+    assert_eq!(header.get("EXPOSURE"), None);
+
+    // Saving with the header keeps every line, in order
+    let (image, _) = radiant::load_with_header(&reader[..]).unwrap();
+    let mut bytes = Vec::new();
+    radiant::save_with_header(&image, &header, &mut bytes).unwrap();
+    assert!(bytes.starts_with(&reader[..reader.len() - 14]));
+    let (_, saved) = radiant::load_with_header(&bytes[..]).unwrap();
+    assert_eq!(saved.lines().collect::<Vec<_>>(), lines);
+}
+
+#[test]
+fn header_get_last() {
+    let reader = b"#?RADIANCE\n\
+        FORMAT=32-bit_rle_rgbe\n\
+        MADE_UP=one\n\
+        MADE_UP = two=three \n\
+        \n-Y 1 +X 1\n\xff\x00\xff\x80";
+    let (_, header) = radiant::load_with_header(&reader[..]).unwrap();
+    assert_eq!(header.get("MADE_UP"), Some("two=three"));
+    assert_eq!(header.get("FORMAT"), Some("32-bit_rle_rgbe"));
+}
+
+#[test]
+fn header_raw_text() {
+    let text = "# made by hand\n\
+        SOFTWARE= radiant tests \n\
+        FORMAT=32-bit_rle_rgbe\n\
+        X-STUDIO-SHOT=42/7 take=3\n\
+        pfilt -x 512 -y 512 in.hdr\n";
+    let mut bytes = format!("#?RADIANCE\n{}\n-Y 1 +X 1\n", text).into_bytes();
+    bytes.extend_from_slice(b"\xff\x00\xff\x80");
+    let (_, header) = radiant::load_with_header(&bytes[..]).unwrap();
+    assert_eq!(header.raw_text(), text);
+
+    let (_, header) =
+        radiant::load_with_header(&b"#?RADIANCE\n\n-Y 1 +X 1\n\xff\x00\xff\x80"[..]).unwrap();
+    assert_eq!(header.raw_text(), "");
+}
+
+#[test]
+fn header_raw_bytes_round_trip() {
+    let lines = &b"# made by hand\r\nSOFTWARE=caf\xe9\r\nFORMAT=32-bit_rle_rgbe\rEXPOSURE=2\n"[..];
+    let mut bytes = b"#?RADIANCE\r\n".to_vec();
+    bytes.extend_from_slice(lines);
+    bytes.extend_from_slice(b"\r\n-Y 1 +X 1\r\n\xff\x00\xff\x80");
+
+    let (_, header) = radiant::load_with_header(&bytes[..]).unwrap();
+    assert_eq!(header.raw_bytes, lines);
+    assert_eq!(header.raw_text(), String::from_utf8_lossy(lines));
+
+    // Lines that span reads are kept the same way
+    let reader = std::io::BufReader::with_capacity(3, &bytes[..]);
+    let decoder = radiant::Decoder::new(reader).unwrap();
+    assert_eq!(decoder.header().raw_bytes, lines);
+}
@@ -0,0 +1,70 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Counts the allocations made by the current thread.
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    let count = ALLOCATIONS.with(Cell::get) - before;
+    drop(result);
+    count
+}
+
+const PIXELS: &[u8] = b"-Y 2 +X 1\n\xff\x00\xff\x80\x00\xff\x00\x80";
+
+fn file(header: &[u8]) -> Vec<u8> {
+    let mut bytes = header.to_vec();
+    bytes.extend_from_slice(PIXELS);
+    bytes
+}
+
+#[test]
+fn header_alloc_free() {
+    let minimal = file(b"#?RADIANCE\n\n");
+    let busy = file(
+        b"#?RGBE \n\
+        # made by hand\n\
+        pfilt -x 512 -y 512 in.hdr\n\
+        SOFTWARE= radiant tests\n\
+        FORMAT=32-bit_rle_rgbe\n\
+        EXPOSURE=2\n\
+        VIEW= -vtv -vp 0 0 0 -vd 0 1 0\n\
+        PRIMARIES=0.64 0.33 0.3 0.6 0.15 0.06 0.3127 0.329\n\
+        MADE_UP=one=two \n\
+        FORMAT = 32-bit_rle_xyze\r\n\
+        \n\n",
+    );
+
+    // Warm up anything that is set up once, like a thread pool
+    radiant::load(&minimal[..]).unwrap();
+
+    let expected = allocations(|| radiant::load(&minimal[..]).unwrap());
+    assert_eq!(allocations(|| radiant::load(&busy[..]).unwrap()), expected);
+    assert_eq!(
+        radiant::load(&busy[..]).unwrap().color_format,
+        radiant::ColorFormat::Xyze
+    );
+
+    // Keeping the header does allocate
+    assert!(allocations(|| radiant::load_with_header(&busy[..]).unwrap()) > expected);
+}
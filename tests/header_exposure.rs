@@ -0,0 +1,61 @@
+use radiant::{LoadError, Warning, RGB};
+
+/// One pixel with two exposure adjustments, making it 0.5 times as bright as it was captured.
+const TWO_EXPOSURES: &[u8] = b"#?RADIANCE\n\
+    EXPOSURE=2\n\
+    FORMAT=32-bit_rle_rgbe\n\
+    EXPOSURE= 0.25\n\
+    \n-Y 1 +X 1\n\x80\x40\xff\x81";
+
+fn with_exposure(value: &str) -> Vec<u8> {
+    let mut bytes = format!("#?RADIANCE\nEXPOSURE={}\n\n-Y 1 +X 1\n", value).into_bytes();
+    bytes.extend_from_slice(b"\x80\x40\xff\x81");
+    bytes
+}
+
+#[test]
+fn exposure_product() {
+    let (mut image, header) = radiant::load_with_header(TWO_EXPOSURES).unwrap();
+    assert_eq!(header.exposure, Some(0.5));
+
+    let stored = image.data[0];
+    image.apply_header_exposure(&header);
+    assert_eq!(image.data[0], stored / 0.5);
+    assert_eq!(
+        image.data[0],
+        RGB {
+            r: 128.0 / 255.0 * 4.0,
+            g: 64.0 / 255.0 * 4.0,
+            b: 255.0 / 255.0 * 4.0,
+        }
+    );
+}
+
+#[test]
+fn exposure_missing() {
+    let bytes = with_exposure("1");
+    let (mut image, mut header) = radiant::load_with_header(&bytes[..]).unwrap();
+    header.exposure = None;
+    let stored = image.data.clone();
+    image.apply_header_exposure(&header);
+    assert_eq!(image.data, stored);
+}
+
+#[test]
+fn exposure_invalid_values() {
+    for value in ["0", "-2", "bright", "inf"] {
+        let bytes = with_exposure(value);
+
+        let (_, header) = radiant::load_with_header(&bytes[..]).unwrap();
+        assert_eq!(header.exposure, None, "{}", value);
+
+        let (_, warnings) = radiant::load_lenient(&bytes[..]).unwrap();
+        assert_eq!(warnings, [Warning::InvalidExposure(value.to_owned())]);
+
+        assert!(matches!(
+            radiant::load_strict(&bytes[..]),
+            Err(LoadError::FileFormat)
+        ));
+    }
+    assert!(radiant::load_strict(TWO_EXPOSURES).is_ok());
+}
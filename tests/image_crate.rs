@@ -0,0 +1,22 @@
+#![cfg(feature = "image")]
+
+use image::Rgb32FImage;
+
+#[test]
+fn into_rgb32f_image() {
+    // Stored from bottom to top, to check that the result is not flipped
+    let reader = b"#?RADIANCE\n\n+Y 2 +X 2\n\
+        \xff\x00\x00\x80\x00\xff\x00\x80\
+        \x00\x00\xff\x80\xff\xff\xff\x80";
+    let image = radiant::load(&reader[..]).unwrap();
+    let top_left = *image.pixel(0, 0);
+
+    let converted = Rgb32FImage::from(image);
+    assert_eq!(converted.dimensions(), (2, 2));
+    assert_eq!(
+        converted.get_pixel(0, 0).0,
+        [top_left.r, top_left.g, top_left.b]
+    );
+    assert_eq!(converted.get_pixel(0, 0).0, [0.0, 0.0, 1.0]);
+    assert_eq!(converted.get_pixel(1, 1).0, [0.0, 1.0, 0.0]);
+}
@@ -0,0 +1,63 @@
+use radiant::{ColorFormat, Image, Limit, LoadError, Orientation, RGB};
+
+#[test]
+fn image_new_checks_length() {
+    let image = Image::new(3, 2, vec![RGB::splat(1.0); 6]).unwrap();
+    assert_eq!((image.width, image.height), (3, 2));
+    assert_eq!(image.orientation, Orientation::default());
+    assert_eq!(image.color_format, ColorFormat::Rgbe);
+
+    assert!(matches!(
+        Image::new(3, 2, vec![RGB::default(); 5]),
+        Err(LoadError::BufferSize {
+            expected: 6,
+            actual: 5
+        })
+    ));
+    assert!(matches!(
+        Image::new(usize::MAX, 2, Vec::new()),
+        Err(LoadError::LimitExceeded(Limit::Pixels))
+    ));
+    assert!(Image::new(0, 7, Vec::new()).is_ok());
+}
+
+#[test]
+fn image_from_fn_round_trip() {
+    let image = Image::from_fn(5, 3, |x, y| RGB {
+        r: x as f32,
+        g: y as f32,
+        b: 0.5,
+    });
+    assert_eq!(image.data.len(), 15);
+    assert_eq!(
+        *image.pixel(4, 1),
+        RGB {
+            r: 4.0,
+            g: 1.0,
+            b: 0.5
+        }
+    );
+
+    let bytes = radiant::encode(&image).unwrap();
+    let loaded = radiant::load(&bytes[..]).unwrap();
+    assert_eq!((loaded.width, loaded.height), (5, 3));
+    for (a, b) in loaded.data.iter().zip(&image.data) {
+        // A shared exponent leaves 8 bits of precision relative to the brightest channel
+        let tolerance = b.r.max(b.g).max(b.b) / 128.0;
+        assert!((a.r - b.r).abs() <= tolerance, "{:?} != {:?}", a, b);
+        assert!((a.g - b.g).abs() <= tolerance, "{:?} != {:?}", a, b);
+    }
+}
+
+#[test]
+fn image_len_and_dimensions() {
+    let image = Image::new(3, 2, vec![RGB::splat(1.0); 6]).unwrap();
+    assert_eq!(image.len(), 6);
+    assert!(!image.is_empty());
+    assert_eq!(image.dimensions(), (3, 2));
+
+    let empty = Image::new(0, 7, Vec::new()).unwrap();
+    assert_eq!(empty.len(), 0);
+    assert!(empty.is_empty());
+    assert_eq!(empty.dimensions(), (0, 7));
+}
@@ -0,0 +1,121 @@
+use radiant::RGB;
+
+fn load() -> radiant::Image {
+    let reader = b"#?RADIANCE\n\n-Y 2 +X 2\n\xff\x00\x00\x80\x00\xff\x00\x80\x00\x00\xff\x80\xff\xff\xff\x80";
+    radiant::load(&reader[..]).unwrap()
+}
+
+#[test]
+fn index_matches_pixel() {
+    let image = load();
+    for y in 0..2 {
+        for x in 0..2 {
+            assert_eq!(image[(x, y)], *image.pixel(x, y));
+        }
+    }
+    assert_eq!(image[(1, 0)].g, 1.0);
+    assert_eq!(image[(0, 1)].b, 1.0);
+}
+
+#[test]
+fn index_mut() {
+    let mut image = load();
+    let black = RGB {
+        r: 0.0,
+        g: 0.0,
+        b: 0.0,
+    };
+    image[(1, 1)] = black;
+    assert_eq!(image.data[3], black);
+}
+
+#[test]
+#[should_panic]
+fn index_out_of_bounds() {
+    let image = load();
+    let _ = image[(0, 2)];
+}
+
+#[test]
+fn get_pixel_bounds() {
+    let image = load();
+    assert_eq!(image.get_pixel(1, 1), Some(image.pixel(1, 1)));
+    assert_eq!(image.get_pixel(2, 0), None);
+    assert_eq!(image.get_pixel(0, 2), None);
+    assert_eq!(image.get_pixel(usize::MAX, usize::MAX), None);
+}
+
+#[test]
+fn get_pixel_mut() {
+    let mut image = load();
+    image.get_pixel_mut(0, 1).unwrap().r = 5.0;
+    assert_eq!(image.data[2].r, 5.0);
+    assert!(image.get_pixel_mut(2, 1).is_none());
+}
+
+#[test]
+fn pixel_mut() {
+    let mut image = load();
+    let pixel = image.pixel_mut(1, 0);
+    std::mem::swap(&mut pixel.r, &mut pixel.g);
+    assert_eq!(image.data[1].r, 1.0);
+    assert_eq!(image.data[1].g, 0.0);
+}
+
+#[test]
+#[should_panic]
+fn pixel_mut_out_of_bounds() {
+    load().pixel_mut(0, 2);
+}
+
+#[test]
+fn rows() {
+    let image = load();
+    let rows: Vec<_> = image.rows().collect();
+    assert_eq!(rows, [&image.data[..2], &image.data[2..]]);
+}
+
+#[test]
+fn rows_mut() {
+    let mut image = load();
+    for row in image.rows_mut() {
+        row.reverse();
+    }
+    assert_eq!(image.data[0], *load().pixel(1, 0));
+    assert_eq!(image.data[3], *load().pixel(0, 1));
+}
+
+#[test]
+fn rows_zero_width() {
+    let image = radiant::load(&b"#?RADIANCE\n\n-Y 3 +X 0\n"[..]).unwrap();
+    assert_eq!(image.rows().count(), 0);
+}
+
+#[test]
+fn into_iter() {
+    let mut image = load();
+    let mut count = 0;
+    for pixel in &image {
+        assert_eq!(pixel, &image.data[count]);
+        count += 1;
+    }
+    assert_eq!(count, 4);
+
+    for pixel in &mut image {
+        pixel.r += 1.0;
+    }
+    assert_eq!(image.data[0].r, 2.0);
+}
+
+#[test]
+fn enumerate_pixels() {
+    let image = load();
+    let coordinates: Vec<_> = image
+        .enumerate_pixels()
+        .map(|(x, y, pixel)| {
+            assert_eq!(pixel, image.pixel(x, y));
+            (x, y)
+        })
+        .collect();
+    assert_eq!(coordinates, [(0, 0), (1, 0), (0, 1), (1, 1)]);
+}
@@ -0,0 +1,83 @@
+use radiant::{LoadError, PushDecoder};
+
+fn file(resolution: &[u8]) -> Vec<u8> {
+    let mut bytes = b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n".to_vec();
+    bytes.extend_from_slice(resolution);
+    bytes.extend_from_slice(b"\xff\x00\xff\x80");
+    bytes
+}
+
+fn invalid_line(result: Result<impl std::fmt::Debug, LoadError>) -> String {
+    match result {
+        Err(LoadError::InvalidDimensions(line)) => line,
+        other => panic!("expected InvalidDimensions, got {:?}", other),
+    }
+}
+
+#[test]
+fn invalid_dimensions_reports_line() {
+    for line in ["-Y one +X 1", "+X 1 +X 1", "-Y 1", "Y 1 X 1"] {
+        let bytes = file(format!("{}\n", line).as_bytes());
+        assert_eq!(invalid_line(radiant::load(&bytes[..])), line);
+        assert_eq!(invalid_line(radiant::read_header(&bytes[..])), line);
+        assert_eq!(invalid_line(radiant::load_lenient(&bytes[..])), line);
+        assert_eq!(invalid_line(PushDecoder::new().feed(&bytes)), line);
+    }
+}
+
+#[test]
+fn invalid_dimensions_crlf() {
+    let bytes = file(b"-Y 1 +Z 1\r\n");
+    assert_eq!(invalid_line(radiant::load(&bytes[..])), "-Y 1 +Z 1");
+}
+
+#[test]
+fn invalid_dimensions_message() {
+    let bytes = file(b"-Y 1 +X one\n");
+    let error = radiant::load(&bytes[..]).unwrap_err();
+    assert_eq!(error.to_string(), "invalid resolution line \"-Y 1 +X one\"");
+}
+
+#[test]
+fn resolution_whitespace_tolerated() {
+    for line in [
+        "-Y 1 +X 2",
+        "-Y 1\t+X 2 ",
+        "  -Y\t1  +X   2",
+        "-Y\t\t1 +X 2\t \t",
+        "-Y 1 +X 2\x0c",
+        "-Y 01 +X 0002",
+    ] {
+        let bytes = file(format!("{}\n", line).as_bytes());
+        let info = radiant::read_header(&bytes[..]).unwrap();
+        assert_eq!((info.width, info.height), (2, 1), "{:?}", line);
+    }
+}
+
+#[test]
+fn resolution_garbage_rejected() {
+    for line in [
+        "-Y 1 +X 2 +Z 3",
+        "-Y 1 +X 2 junk",
+        "-Y 1 +X 2 3",
+        "-Y 1 +X",
+        "-Y1+X 2x",
+        "-Y 1 -Y 2",
+        "-Y 1 2 +X",
+        "- Y 1 +X 2",
+        "-Y -1 +X 2",
+        "-Y 1.5 +X 2",
+        " \t ",
+    ] {
+        let bytes = file(format!("{}\n", line).as_bytes());
+        assert!(
+            matches!(
+                radiant::read_header(&bytes[..]),
+                Err(LoadError::InvalidDimensions(_))
+            ),
+            "{:?}",
+            line
+        );
+        assert!(radiant::load_lenient(&bytes[..]).is_err(), "{:?}", line);
+    }
+}
@@ -0,0 +1,167 @@
+use radiant::{Limit, LoadError, LoadLimits};
+use std::io::{BufReader, Read};
+
+fn limited(resolution: &str, limits: &LoadLimits) -> Result<radiant::Image, LoadError> {
+    let bytes = format!("#?RADIANCE\n\n{}\n", resolution).into_bytes();
+    radiant::load_with_limits(&bytes[..], limits)
+}
+
+#[test]
+fn limits_absurd_dimensions() {
+    let result = limited("-Y 1000000 +X 1000000", &LoadLimits::default());
+    assert!(matches!(
+        result,
+        Err(LoadError::LimitExceeded(Limit::Width))
+    ));
+}
+
+#[test]
+fn limits_each_limit() {
+    let limits = LoadLimits {
+        max_width: 100,
+        max_height: 50,
+        max_pixels: 1000,
+        max_alloc_bytes: 6000,
+        ..LoadLimits::none()
+    };
+    let tripped = |resolution| match limited(resolution, &limits) {
+        Err(LoadError::LimitExceeded(limit)) => Some(limit),
+        _ => None,
+    };
+    assert_eq!(tripped("-Y 1 +X 101"), Some(Limit::Width));
+    assert_eq!(tripped("-Y 51 +X 1"), Some(Limit::Height));
+    assert_eq!(tripped("-Y 50 +X 21"), Some(Limit::Pixels));
+    assert_eq!(tripped("-Y 10 +X 60"), Some(Limit::AllocBytes));
+    assert_eq!(tripped("-Y 10 +X 10"), None);
+}
+
+#[test]
+fn limits_allow_small_images() {
+    let reader = b"#?RADIANCE\n\n-Y 1 +X 1\n\xff\x00\xff\x80";
+    let image = radiant::load_with_limits(&reader[..], &LoadLimits::default()).unwrap();
+    assert_eq!(image.data, radiant::load(&reader[..]).unwrap().data);
+}
+
+/// A reader that sends `byte` forever, counting how many bytes were read.
+struct Endless {
+    byte: u8,
+    count: usize,
+}
+
+impl Read for Endless {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        buf.fill(self.byte);
+        self.count += buf.len();
+        Ok(buf.len())
+    }
+}
+
+fn endless_header(byte: u8) -> (Result<radiant::Image, LoadError>, usize) {
+    let start = &b"#?RADIANCE\nSOFTWARE="[..];
+    let mut endless = Endless { byte, count: 0 };
+    let reader = BufReader::new(start.chain(&mut endless));
+    let result = radiant::load_with_limits(reader, &LoadLimits::default());
+    (result, endless.count)
+}
+
+#[test]
+fn limits_endless_header_line() {
+    let (result, read) = endless_header(b'x');
+    assert!(matches!(
+        result,
+        Err(LoadError::LimitExceeded(Limit::HeaderLineBytes))
+    ));
+    assert!(read < 64 << 10, "read {} bytes", read);
+}
+
+#[test]
+fn limits_endless_header() {
+    // Millions of one byte lines
+    let start = &b"#?RADIANCE\n"[..];
+    let lines = b"X=Y\n".repeat(1 << 20);
+    let reader = start.chain(&lines[..]);
+    let result = radiant::load_with_limits(reader, &LoadLimits::default());
+    assert!(matches!(
+        result,
+        Err(LoadError::LimitExceeded(Limit::HeaderBytes))
+    ));
+
+    // Empty lines are counted too
+    let (result, read) = endless_header(b'\n');
+    assert!(matches!(
+        result,
+        Err(LoadError::LimitExceeded(Limit::HeaderBytes))
+    ));
+    assert!(read < 2 << 20, "read {} bytes", read);
+}
+
+#[test]
+fn limits_header_sizes() {
+    let software = "x".repeat(5000);
+    let mut bytes = format!("#?RADIANCE\nSOFTWARE={}\n\n-Y 1 +X 1\n", software).into_bytes();
+    let header_len = bytes.len();
+    bytes.extend_from_slice(b"\xff\x00\xff\x80");
+    assert!(radiant::load_with_limits(&bytes[..], &LoadLimits::default()).is_ok());
+
+    let tripped = |limits: LoadLimits| match radiant::load_with_limits(&bytes[..], &limits) {
+        Err(LoadError::LimitExceeded(limit)) => Some(limit),
+        Ok(_) => None,
+        Err(e) => panic!("unexpected error {:?}", e),
+    };
+    let line = |max| LoadLimits {
+        max_header_line_bytes: max,
+        ..LoadLimits::none()
+    };
+    let total = |max| LoadLimits {
+        max_header_bytes: max,
+        ..LoadLimits::none()
+    };
+    assert_eq!(tripped(line(5008)), Some(Limit::HeaderLineBytes));
+    assert_eq!(tripped(line(5009)), None);
+    assert_eq!(tripped(total(header_len - 1)), Some(Limit::HeaderBytes));
+    assert_eq!(tripped(total(header_len)), None);
+
+    // The default limits allow it
+    assert!(radiant::load(&bytes[..]).is_ok());
+}
+
+#[test]
+fn limits_default_loaders_limit_header() {
+    let mut bytes = b"#?RADIANCE
+SOFTWARE="
+        .to_vec();
+    bytes.resize(bytes.len() + (4 << 20), b'x');
+    bytes.extend_from_slice(b"\n\n-Y 1 +X 1\n\xff\x00\xff\x80");
+    let line_exceeded = |result| {
+        matches!(
+            result,
+            Err(LoadError::LimitExceeded(Limit::HeaderLineBytes))
+        )
+    };
+
+    assert!(line_exceeded(radiant::load(&bytes[..]).map(|_| ())));
+    assert!(line_exceeded(
+        radiant::load_with_header(&bytes[..]).map(|_| ())
+    ));
+    assert!(line_exceeded(radiant::load_lenient(&bytes[..]).map(|_| ())));
+    assert!(line_exceeded(radiant::read_header(&bytes[..]).map(|_| ())));
+    assert!(line_exceeded(radiant::Decoder::new(&bytes[..]).map(|_| ())));
+    assert!(line_exceeded(
+        radiant::PushDecoder::new().feed(&bytes).map(|_| ())
+    ));
+}
+
+#[test]
+fn limits_default_loaders_endless_header_line() {
+    let start = &b"#?RADIANCE\nSOFTWARE="[..];
+    let mut endless = Endless {
+        byte: b'x',
+        count: 0,
+    };
+    let result = radiant::load(BufReader::new(start.chain(&mut endless)));
+    assert!(matches!(
+        result,
+        Err(LoadError::LimitExceeded(Limit::HeaderLineBytes))
+    ));
+    assert!(endless.count < 64 << 10, "read {} bytes", endless.count);
+}
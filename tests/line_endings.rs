@@ -0,0 +1,103 @@
+use radiant::{ColorFormat, PushDecoder};
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+const PIXEL: &[u8] = b"\xff\x00\xff\x80";
+
+fn file(header: &[u8]) -> Vec<u8> {
+    let mut bytes = header.to_vec();
+    bytes.extend_from_slice(PIXEL);
+    bytes
+}
+
+fn check(header: &[u8]) {
+    let bytes = file(header);
+    let (image, header) = radiant::load_with_header(&bytes[..]).unwrap();
+    assert_eq!((image.width, image.height), (1, 1));
+    assert_eq!(image.data[0].b, 1.0);
+    assert_eq!(header.format, Some(ColorFormat::Rgbe));
+    assert_eq!(header.exposure, Some(2.0));
+    assert!(header.raw.iter().all(|line| !line.contains('\r')));
+
+    // Lines split across reads
+    let tiny = std::io::BufReader::with_capacity(1, &bytes[..]);
+    assert_eq!(radiant::load(tiny).unwrap().data, image.data);
+
+    let (counted, consumed) = radiant::load_counted(&bytes[..]).unwrap();
+    assert_eq!(counted.data, image.data);
+    assert_eq!(consumed, bytes.len() as u64);
+
+    for chunk_size in 1..=bytes.len() {
+        let mut decoder = PushDecoder::new();
+        for chunk in bytes.chunks(chunk_size) {
+            decoder.feed(chunk).unwrap();
+        }
+        assert_eq!(decoder.finish().unwrap().data, image.data);
+    }
+}
+
+#[test]
+fn crlf_header() {
+    check(b"#?RADIANCE\r\nFORMAT=32-bit_rle_rgbe\r\nEXPOSURE=2\r\n\r\n-Y 1 +X 1\r\n");
+}
+
+#[test]
+fn spaces_around_equals() {
+    check(b"#?RADIANCE\nFORMAT = 32-bit_rle_rgbe \n EXPOSURE=  2\n\n-Y 1 +X 1\n");
+}
+
+#[test]
+fn blank_lines_before_resolution() {
+    check(b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\nEXPOSURE=2\n\n\n\r\n-Y 1 +X 1\n");
+}
+
+#[test]
+fn mixed_line_endings() {
+    check(b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\r\nEXPOSURE=2\n\r\n-Y 1 +X 1\n");
+}
+
+#[test]
+fn cr_header() {
+    check(b"#?RADIANCE\rFORMAT=32-bit_rle_rgbe\rEXPOSURE=2\r\r-Y 1 +X 1\r");
+}
+
+#[test]
+fn cr_and_crlf_mixed() {
+    check(b"#?RADIANCE\rFORMAT=32-bit_rle_rgbe\r\nEXPOSURE=2\n\r\r\n-Y 1 +X 1\r\n");
+}
+
+/// Replace the LF at the end of every header line, up to and including the resolution line.
+fn with_line_ending(ending: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut rest = HDR;
+    for _ in 0..4 {
+        let end = rest.iter().position(|&b| b == b'\n').unwrap();
+        bytes.extend_from_slice(&rest[..end]);
+        bytes.extend_from_slice(ending);
+        rest = &rest[end + 1..];
+    }
+    assert!(bytes[..bytes.len() - ending.len()].ends_with(b"-Y 4 +X 16"));
+    bytes.extend_from_slice(rest);
+    bytes
+}
+
+#[test]
+fn line_endings_decode_identically() {
+    let (expected, expected_header) = radiant::load_with_header(HDR).unwrap();
+    for ending in [&b"\r\n"[..], b"\r"] {
+        let bytes = with_line_ending(ending);
+        let (image, header) = radiant::load_with_header(&bytes[..]).unwrap();
+        assert_eq!(
+            (image.width, image.height),
+            (expected.width, expected.height)
+        );
+        assert_eq!(image.data, expected.data);
+        assert_eq!(header.raw, expected_header.raw);
+        assert!(radiant::load_strict(&bytes[..]).is_ok());
+
+        let mut decoder = PushDecoder::new();
+        let progress = decoder.feed(&bytes).unwrap();
+        assert_eq!(progress.consumed, bytes.len());
+        assert_eq!(decoder.finish().unwrap().data, expected.data);
+    }
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "tokio")]
+
+use radiant::LoadError;
+use tokio::io::BufReader;
+
+const IMAGE: &[u8] = b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y 2 +X 8\n\
+    \x02\x02\x00\x08\
+    \x84\x10\x04\x20\x21\x22\x23\x88\x00\x88\x81\x88\x80\
+    \xff\x00\xff\x80\x01\x01\x01\x07";
+
+fn chunked(bytes: &[u8], chunk_size: usize) -> tokio_test::io::Mock {
+    let mut builder = tokio_test::io::Builder::new();
+    for chunk in bytes.chunks(chunk_size) {
+        builder.read(chunk);
+    }
+    builder.build()
+}
+
+#[tokio::test]
+async fn load_async_matches_load() {
+    let expected = radiant::load(IMAGE).unwrap();
+    for chunk_size in 1..=IMAGE.len() {
+        let image = radiant::load_async(BufReader::new(chunked(IMAGE, chunk_size)))
+            .await
+            .unwrap();
+        assert_eq!(image.width, expected.width);
+        assert_eq!(image.height, expected.height);
+        assert_eq!(image.data, expected.data, "chunk size {}", chunk_size);
+    }
+}
+
+#[tokio::test]
+async fn load_async_crlf_header() {
+    let bytes = b"#?RADIANCE\r\nFORMAT=32-bit_rle_rgbe\r\n\r\n\r\n-Y 1 +X 1\r\n\xff\x00\xff\x80";
+    let expected = radiant::load(&bytes[..]).unwrap();
+    let image = radiant::load_async(&bytes[..]).await.unwrap();
+    assert_eq!(image.data, expected.data);
+
+    let bytes = b"#?RADIANCE\rFORMAT=32-bit_rle_rgbe\r\r\r\n-Y 1 +X 1\r\xff\x00\xff\x80";
+    for chunk_size in 1..=bytes.len() {
+        let image = radiant::load_async(BufReader::new(chunked(bytes, chunk_size)))
+            .await
+            .unwrap();
+        assert_eq!(image.data, expected.data, "chunk size {}", chunk_size);
+    }
+}
+
+#[tokio::test]
+async fn load_async_truncated() {
+    for len in 0..IMAGE.len() {
+        let result = radiant::load_async(BufReader::new(chunked(&IMAGE[..len], 3))).await;
+        assert!(
+            matches!(result, Err(LoadError::Eof(_))),
+            "truncated to {} bytes: {:?}",
+            len,
+            result
+        );
+    }
+}
+
+#[tokio::test]
+async fn load_async_long_header_line() {
+    let mut bytes = b"#?RADIANCE\nSOFTWARE=".to_vec();
+    bytes.resize(bytes.len() + (4 << 20), b'x');
+    bytes.extend_from_slice(b"\n\n-Y 1 +X 1\n\xff\x00\xff\x80");
+    let result = radiant::load_async(&bytes[..]).await;
+    assert!(matches!(
+        result,
+        Err(LoadError::LimitExceeded(radiant::Limit::HeaderLineBytes))
+    ));
+}
+
+#[tokio::test]
+async fn load_async_color_format() {
+    let bytes = b"#?RADIANCE\nFORMAT=32-bit_rle_xyze\n\n-Y 1 +X 1\n\xff\x00\xff\x80";
+    let image = radiant::load_async(BufReader::new(chunked(bytes, 4)))
+        .await
+        .unwrap();
+    assert_eq!(image.color_format, radiant::ColorFormat::Xyze);
+}
+
+#[tokio::test]
+async fn load_async_unsupported_format() {
+    let bytes = b"#?RADIANCE\nFORMAT=bogus\n\n-Y 1 +X 1\n\xff\x00\xff\x80";
+    let result = radiant::load_async(&bytes[..]).await;
+    assert!(matches!(result, Err(LoadError::UnsupportedFormat(format)) if format == "bogus"));
+}
+
+#[tokio::test]
+async fn load_async_gzip() {
+    let result = radiant::load_async(&b"\x1f\x8b\x08\x00"[..]).await;
+    assert!(matches!(result, Err(LoadError::Gzip)));
+}
+
+#[tokio::test]
+async fn load_async_run_across_scanlines() {
+    let bytes = b"#?RADIANCE\n\n-Y 3 +X 2\n\
+        \xff\x00\x00\x80\x01\x01\x01\x02\
+        \x00\x00\xff\x80\x01\x01\x01\x02";
+    let expected = radiant::load(&bytes[..]).unwrap();
+    let image = radiant::load_async(&bytes[..]).await.unwrap();
+    assert_eq!(image.data, expected.data);
+}
@@ -0,0 +1,70 @@
+use radiant::LoadError;
+use std::io::{BufReader, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+/// A reader that stops once `pause_at` bytes have been read, until it is told to continue.
+struct Pausing<R> {
+    inner: R,
+    read: usize,
+    pause_at: usize,
+    paused: Sender<()>,
+    resume: Receiver<()>,
+}
+
+impl<R: Read> Read for Pausing<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.read >= self.pause_at {
+            self.paused.send(()).unwrap();
+            self.resume.recv().unwrap();
+            self.pause_at = usize::MAX;
+        }
+        let len = buf.len().min(self.pause_at - self.read);
+        let read = self.inner.read(&mut buf[..len])?;
+        self.read += read;
+        Ok(read)
+    }
+}
+
+/// A 1000x1000 image of flat, identical pixels.
+fn large_image() -> impl Read {
+    let header = &b"#?RADIANCE\n\n-Y 1000 +X 1000\n"[..];
+    header.chain(std::io::repeat(0x80).take(1000 * 1000 * 4))
+}
+
+#[test]
+fn cancellable_completes() {
+    let cancel = AtomicBool::new(false);
+    let image = radiant::load_cancellable(BufReader::new(large_image()), &cancel).unwrap();
+    assert_eq!(image.data.len(), 1000 * 1000);
+}
+
+#[test]
+fn cancellable_mid_decode() {
+    let cancel = AtomicBool::new(false);
+    let (paused, paused_rx) = channel();
+    let (resume_tx, resume) = channel();
+    let reader = BufReader::new(Pausing {
+        inner: large_image(),
+        read: 0,
+        pause_at: 100 * 1000 * 4,
+        paused,
+        resume,
+    });
+
+    let result = std::thread::scope(|scope| {
+        let decode = scope.spawn(|| radiant::load_cancellable(reader, &cancel));
+        paused_rx.recv().unwrap();
+        cancel.store(true, Ordering::Relaxed);
+        resume_tx.send(()).unwrap();
+        decode.join().unwrap()
+    });
+    assert!(matches!(result, Err(LoadError::Cancelled)));
+}
+
+#[test]
+fn cancellable_before_start() {
+    let cancel = AtomicBool::new(true);
+    let result = radiant::load_cancellable(BufReader::new(large_image()), &cancel);
+    assert!(matches!(result, Err(LoadError::Cancelled)));
+}
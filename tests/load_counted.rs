@@ -0,0 +1,31 @@
+use std::io::{BufRead, BufReader, Read};
+
+const FIRST: &[u8] = b"#?RADIANCE\0\n\n-Y 1 +X 8\n\
+    \x02\x02\x00\x08\
+    \x88\xff\x88\x00\x88\xff\x88\x80";
+
+const SECOND: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+#[test]
+fn load_counted_reports_length() {
+    let (_, count) = radiant::load_counted(FIRST).unwrap();
+    assert_eq!(count, FIRST.len() as u64);
+}
+
+#[test]
+fn load_counted_concatenated() {
+    let bytes = [FIRST, SECOND, b"trailer"].concat();
+    let mut reader = BufReader::with_capacity(16, &bytes[..]);
+
+    let (first, first_count) = radiant::load_counted(&mut reader).unwrap();
+    let (second, second_count) = radiant::load_counted(&mut reader).unwrap();
+    assert_eq!(first_count, FIRST.len() as u64);
+    assert_eq!(second_count, SECOND.len() as u64);
+    assert_eq!(first.data, radiant::load(FIRST).unwrap().data);
+    assert_eq!(second.data, radiant::load(SECOND).unwrap().data);
+
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"trailer");
+    assert!(reader.fill_buf().unwrap().is_empty());
+}
@@ -0,0 +1,28 @@
+use radiant::LoadError;
+
+#[test]
+fn load_dimensions_header_only() {
+    let reader = b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y 768 +X 1024\n";
+    assert_eq!(radiant::load_dimensions(&reader[..]).unwrap(), (1024, 768));
+}
+
+#[test]
+fn load_dimensions_matches_load() {
+    let reader = b"#?RADIANCE\0\n\n-Y 2 +X 2\n\
+                 \xff\x00\xff\x80\x01\x01\x01\x01\
+                 \x00\xff\x00\x80\x01\x01\x01\x01";
+    let image = radiant::load(&reader[..]).unwrap();
+    assert_eq!(
+        radiant::load_dimensions(&reader[..]).unwrap(),
+        (image.width, image.height)
+    );
+}
+
+#[test]
+fn load_dimensions_bad_magic() {
+    let reader = b"#!RADIANCE\n\n-Y 1 +X 1\n";
+    assert!(matches!(
+        radiant::load_dimensions(&reader[..]),
+        Err(LoadError::FileFormat)
+    ));
+}
@@ -0,0 +1,36 @@
+use std::num::NonZeroUsize;
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+#[test]
+fn downscaled_matches_striding() {
+    let full = radiant::load(HDR).unwrap();
+    for factor in 1..=20 {
+        let image = radiant::load_downscaled(HDR, NonZeroUsize::new(factor).unwrap()).unwrap();
+        assert_eq!(image.width, full.width.div_ceil(factor));
+        assert_eq!(image.height, full.height.div_ceil(factor));
+
+        let expected: Vec<_> = full
+            .rows()
+            .step_by(factor)
+            .flat_map(|row| row.iter().step_by(factor))
+            .copied()
+            .collect();
+        assert_eq!(image.data, expected, "factor {}", factor);
+    }
+}
+
+#[test]
+fn downscaled_column_major() {
+    // A 3x2 image stored as columns from left to right, each from top to bottom
+    let mut bytes = b"#?RADIANCE\n\n+X 3 -Y 2\n".to_vec();
+    for column in 0..3 {
+        for row in 0..2 {
+            bytes.extend_from_slice(&[10 * row + column + 1, 0, 0, 0x80]);
+        }
+    }
+    let full = radiant::load(&bytes[..]).unwrap();
+    let image = radiant::load_downscaled(&bytes[..], NonZeroUsize::new(2).unwrap()).unwrap();
+    assert_eq!((image.width, image.height), (2, 1));
+    assert_eq!(image.data, [*full.pixel(0, 0), *full.pixel(2, 0)]);
+}
@@ -0,0 +1,40 @@
+use radiant::RGB;
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+#[test]
+fn load_into_matches_load() {
+    let mut data = Vec::new();
+    let dimensions = radiant::load_into(HDR, &mut data).unwrap();
+    assert_eq!(dimensions, (16, 4));
+    assert_eq!(data, radiant::load(HDR).unwrap().data);
+}
+
+#[test]
+fn load_into_reuses_buffer() {
+    let mut data = vec![RGB::splat(7.0); 100];
+    let capacity = data.capacity();
+    let pointer = data.as_ptr();
+
+    radiant::load_into(HDR, &mut data).unwrap();
+    assert_eq!(data.len(), 64);
+    assert_eq!(data.capacity(), capacity);
+    assert_eq!(data.as_ptr(), pointer);
+    assert_eq!(data, radiant::load(HDR).unwrap().data);
+}
+
+#[test]
+fn load_into_overwrites_smaller_image() {
+    let mut data = Vec::new();
+    radiant::load_into(HDR, &mut data).unwrap();
+    let reader = b"#?RADIANCE\n\n-Y 1 +X 1\n\xff\x00\xff\x80";
+    assert_eq!(radiant::load_into(&reader[..], &mut data).unwrap(), (1, 1));
+    assert_eq!(
+        data,
+        [RGB {
+            r: 1.0,
+            g: 0.0,
+            b: 1.0
+        }]
+    );
+}
@@ -0,0 +1,32 @@
+use radiant::{LoadError, RGB};
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+#[test]
+fn load_into_slice_matches_load() {
+    let mut data = [RGB::default(); 64];
+    let dimensions = radiant::load_into_slice(HDR, &mut data).unwrap();
+    assert_eq!(dimensions, (16, 4));
+    assert_eq!(&data[..], &radiant::load(HDR).unwrap().data[..]);
+}
+
+#[test]
+fn load_into_slice_leaves_rest_untouched() {
+    let mut data = vec![RGB::splat(7.0); 100];
+    radiant::load_into_slice(HDR, &mut data).unwrap();
+    assert_eq!(data[..64], radiant::load(HDR).unwrap().data[..]);
+    assert!(data[64..].iter().all(|&pixel| pixel == RGB::splat(7.0)));
+}
+
+#[test]
+fn load_into_slice_too_small() {
+    let mut data = [RGB::default(); 63];
+    let result = radiant::load_into_slice(HDR, &mut data);
+    assert!(matches!(
+        result,
+        Err(LoadError::BufferSize {
+            expected: 64,
+            actual: 63
+        })
+    ));
+}
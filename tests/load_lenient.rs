@@ -0,0 +1,80 @@
+use radiant::Warning;
+
+const PIXEL: &[u8] = b"\xff\x00\xff\x80";
+
+fn file(header: &[u8]) -> Vec<u8> {
+    let mut bytes = header.to_vec();
+    bytes.extend_from_slice(PIXEL);
+    bytes
+}
+
+fn lenient(header: &[u8]) -> Vec<Warning> {
+    let bytes = file(header);
+    let (image, warnings) = radiant::load_lenient(&bytes[..]).unwrap();
+    assert_eq!((image.width, image.height), (1, 1));
+    assert_eq!(image.data[0].b, 1.0);
+    warnings
+}
+
+#[test]
+fn lenient_clean_file() {
+    assert_eq!(
+        lenient(b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y 1 +X 1\n"),
+        []
+    );
+}
+
+#[test]
+fn lenient_crlf() {
+    let header = b"#?RADIANCE\r\nFORMAT=32-bit_rle_rgbe\r\n\r\n-Y 1 +X 1\r\n";
+    assert!(radiant::load(&file(header)[..]).is_ok());
+    assert_eq!(lenient(header), [Warning::NonStandardLineEnding]);
+
+    let header = b"#?RADIANCE\rFORMAT=32-bit_rle_rgbe\r\r-Y 1 +X 1\r";
+    assert!(radiant::load(&file(header)[..]).is_ok());
+    assert_eq!(lenient(header), [Warning::NonStandardLineEnding]);
+}
+
+#[test]
+fn lenient_missing_blank_line() {
+    let header = b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n-Y 1 +X 1\n";
+    assert!(radiant::load(&file(header)[..]).is_err());
+    assert_eq!(lenient(header), [Warning::MissingBlankLine]);
+}
+
+#[test]
+fn lenient_sloppy_resolution() {
+    for header in [
+        &b"#?RADIANCE\n\n-y 1 +x 1\n"[..],
+        b"#?RADIANCE\n\n-Y1 +X1\n",
+        b"#?RADIANCE\n\n-Y 1+X 1\n",
+    ] {
+        assert!(radiant::load(&file(header)[..]).is_err());
+        assert_eq!(lenient(header), [Warning::SloppyResolutionLine]);
+    }
+}
+
+#[test]
+fn lenient_header_variables() {
+    let header = b"#?RADIANCE\n\
+        FORMAT=32-bit_rle_rgbe\n\
+        MADE_UP=1\n\
+        pfilt -x 512 -1 out=x\n\
+        # COMMENT=1\n\
+        VIEW= -vtv\n\
+        FORMAT=32-bit_rle_rgbe\n\
+        \n-Y 1 +X 1\n";
+    assert_eq!(
+        lenient(header),
+        [
+            Warning::UnknownVariable("MADE_UP".to_owned()),
+            Warning::DuplicateFormat,
+        ]
+    );
+}
+
+#[test]
+fn lenient_unrecoverable() {
+    let bytes = file(b"#?RADIANCE\n\n-Y one +X 1\n");
+    assert!(radiant::load_lenient(&bytes[..]).is_err());
+}
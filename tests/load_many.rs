@@ -0,0 +1,58 @@
+#![cfg(feature = "rayon")]
+
+use radiant::LoadError;
+use std::io::ErrorKind;
+
+#[test]
+fn load_many_isolates_errors() {
+    let dir = std::env::temp_dir();
+    let valid = dir.join("radiant_load_many_valid.hdr");
+    let truncated = dir.join("radiant_load_many_truncated.hdr");
+    let missing = dir.join("radiant_load_many_missing.hdr");
+
+    let bytes = include_bytes!("../assets/gradient.hdr");
+    std::fs::write(&valid, bytes).unwrap();
+    std::fs::write(&truncated, &bytes[..bytes.len() - 10]).unwrap();
+
+    let paths = [&valid, &truncated, &missing, &valid];
+    let results = radiant::load_many(&paths);
+    std::fs::remove_file(&valid).unwrap();
+    std::fs::remove_file(&truncated).unwrap();
+
+    assert_eq!(results.len(), 4);
+    let expected = radiant::load(&bytes[..]).unwrap();
+    assert_eq!(results[0].as_ref().unwrap().data, expected.data);
+    assert!(matches!(results[1], Err(LoadError::Eof(_))));
+    match &results[2] {
+        Err(LoadError::Io(error)) => assert_eq!(error.kind(), ErrorKind::NotFound),
+        other => panic!("expected not found, got {:?}", other),
+    }
+    assert_eq!(results[3].as_ref().unwrap().data, expected.data);
+}
+
+#[test]
+fn load_many_with_limits_per_file() {
+    let dir = std::env::temp_dir();
+    let small = dir.join("radiant_load_many_small.hdr");
+    let large = dir.join("radiant_load_many_large.hdr");
+
+    std::fs::write(&small, b"#?RADIANCE\n\n-Y 1 +X 1\n\xff\x00\xff\x80").unwrap();
+    std::fs::write(&large, include_bytes!("../assets/gradient.hdr")).unwrap();
+
+    let limits = radiant::LoadLimits {
+        max_width: 8,
+        ..Default::default()
+    };
+    let paths = [&small, &large, &small];
+    let results = radiant::load_many_with_limits(&paths, &limits);
+    std::fs::remove_file(&small).unwrap();
+    std::fs::remove_file(&large).unwrap();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap().width, 1);
+    assert!(matches!(
+        results[1],
+        Err(LoadError::LimitExceeded(radiant::Limit::Width))
+    ));
+    assert_eq!(results[2].as_ref().unwrap().width, 1);
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "mmap")]
+
+use radiant::LoadError;
+use std::io::ErrorKind;
+
+fn with_file<T>(name: &str, contents: &[u8], f: impl FnOnce(&std::path::Path) -> T) -> T {
+    let path = std::env::temp_dir().join(name);
+    std::fs::write(&path, contents).unwrap();
+    let result = f(&path);
+    std::fs::remove_file(&path).unwrap();
+    result
+}
+
+#[test]
+fn load_mmap_matches_load() {
+    let bytes = b"#?RADIANCE\0\n\n-Y 2 +X 8\n\
+        \x02\x02\x00\x08\
+        \x88\xff\x88\x00\x88\xff\x88\x80\
+        \xff\x00\xff\x80\x01\x01\x01\x07";
+    let image = with_file("radiant_load_mmap_matches_load.hdr", bytes, |path| {
+        radiant::load_mmap(path).unwrap()
+    });
+    let expected = radiant::load(&bytes[..]).unwrap();
+    assert_eq!(image.width, expected.width);
+    assert_eq!(image.height, expected.height);
+    let bits = |data: &[radiant::RGB]| {
+        data.iter()
+            .map(|p| [p.r.to_bits(), p.g.to_bits(), p.b.to_bits()])
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(bits(&image.data), bits(&expected.data));
+}
+
+#[test]
+fn load_mmap_empty_file() {
+    let result = with_file("radiant_load_mmap_empty_file.hdr", b"", |path| {
+        radiant::load_mmap(path)
+    });
+    assert!(matches!(result, Err(LoadError::Eof(_))));
+}
+
+#[test]
+fn load_mmap_shorter_than_magic() {
+    let result = with_file("radiant_load_mmap_short.hdr", b"#?RAD", |path| {
+        radiant::load_mmap(path)
+    });
+    assert!(matches!(result, Err(LoadError::Eof(_))));
+}
+
+#[test]
+fn load_mmap_not_found() {
+    let path = std::env::temp_dir().join("radiant_load_mmap_not_found.hdr");
+    match radiant::load_mmap(path) {
+        Err(LoadError::Io(error)) => assert_eq!(error.kind(), ErrorKind::NotFound),
+        other => panic!("expected not found, got {:?}", other),
+    }
+}
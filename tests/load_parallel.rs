@@ -0,0 +1,43 @@
+#![cfg(feature = "rayon")]
+
+use radiant::{Image, RGB};
+use std::ops::ControlFlow;
+
+/// A wide image with the adaptive run-length encoding followed by an old-style one, and some
+/// trailing bytes.
+fn encoded() -> Vec<u8> {
+    let data = (0..300 * 50)
+        .map(|i| RGB::splat((i % 13) as f32 * 0.5))
+        .collect();
+    let image = Image::new(300, 50, data).unwrap();
+    let mut bytes = Vec::new();
+    radiant::save_rle(&image, &mut bytes).unwrap();
+    bytes.extend_from_slice(b"trailing");
+    bytes
+}
+
+fn serial(bytes: &[u8]) -> Vec<RGB> {
+    let mut data = Vec::new();
+    radiant::for_each_scanline(bytes, |_, scanline| {
+        data.extend_from_slice(scanline);
+        ControlFlow::Continue(())
+    })
+    .unwrap();
+    data
+}
+
+#[test]
+fn parallel_matches_serial() {
+    let bytes = encoded();
+    let (image, consumed) = radiant::load_counted(&bytes[..]).unwrap();
+    assert_eq!(image.data, serial(&bytes));
+    assert_eq!(&bytes[consumed as usize..], b"trailing");
+}
+
+#[test]
+fn parallel_small_buffer() {
+    let bytes = encoded();
+    let reader = std::io::BufReader::with_capacity(5, &bytes[..]);
+    let image = radiant::load(reader).unwrap();
+    assert_eq!(image.data, serial(&bytes));
+}
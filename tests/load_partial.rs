@@ -0,0 +1,52 @@
+use radiant::LoadError;
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+const HEADER_LEN: usize = 46;
+const SCANLINE_LEN: usize = 16 * 4;
+
+#[test]
+fn partial_complete() {
+    let image = radiant::load_partial(HDR).unwrap().unwrap();
+    assert_eq!(image.data, radiant::load(HDR).unwrap().data);
+}
+
+#[test]
+fn partial_truncated() {
+    let full = radiant::load(HDR).unwrap();
+    for end in HEADER_LEN + SCANLINE_LEN..HDR.len() {
+        let truncated = &HDR[..end];
+        assert!(matches!(radiant::load(truncated), Err(LoadError::Eof(_))));
+
+        let partial = radiant::load_partial(truncated).unwrap().unwrap_err();
+        let rows = (end - HEADER_LEN) / SCANLINE_LEN;
+        assert_eq!(partial.rows_decoded, rows, "truncated at {}", end);
+        assert!(matches!(partial.error, LoadError::Eof(_)));
+
+        let (decoded, rest) = partial.image.data.split_at(rows * 16);
+        assert_eq!(decoded, &full.data[..rows * 16]);
+        assert!(rest
+            .iter()
+            .all(|pixel| pixel.r == 0.0 && pixel.g == 0.0 && pixel.b == 0.0));
+    }
+}
+
+#[test]
+fn partial_nothing_decoded() {
+    for end in HEADER_LEN..HEADER_LEN + SCANLINE_LEN {
+        assert!(matches!(
+            radiant::load_partial(&HDR[..end]),
+            Err(LoadError::Eof(_))
+        ));
+    }
+}
+
+#[test]
+fn partial_bad_rle() {
+    // The second scanline claims a run longer than the scanline
+    let mut bytes = b"#?RADIANCE\n\n-Y 2 +X 2\n\x00\x00\xff\x80\x00\x00\xff\x80".to_vec();
+    bytes.extend_from_slice(b"\x00\xff\x00\x80\x01\x01\x01\x05");
+    let partial = radiant::load_partial(&bytes[..]).unwrap().unwrap_err();
+    assert_eq!(partial.rows_decoded, 1);
+    assert!(matches!(partial.error, LoadError::Rle));
+    assert_eq!(partial.image.data[2].g, 0.0);
+}
@@ -0,0 +1,41 @@
+use radiant::{LoadError, RGB};
+use std::io::ErrorKind;
+
+#[test]
+fn load_path_reads_file() {
+    let path = std::env::temp_dir().join("radiant_load_path_reads_file.pic");
+    std::fs::write(&path, b"#?RADIANCE\0\n\n-Y 1 +X 1\n\xff\x00\xff\x80").unwrap();
+    let image = radiant::load_path(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let image = image.unwrap();
+    assert_eq!(image.width, 1);
+    assert_eq!(image.height, 1);
+    assert_eq!(
+        &image.data,
+        &[RGB {
+            r: 1.0,
+            g: 0.0,
+            b: 1.0,
+        },]
+    );
+}
+
+#[test]
+fn load_path_not_found() {
+    let path = std::env::temp_dir().join("radiant_load_path_not_found.hdr");
+    match radiant::load_path(path) {
+        Err(LoadError::Io(error)) => assert_eq!(error.kind(), ErrorKind::NotFound),
+        other => panic!("expected not found, got {:?}", other),
+    }
+}
+
+#[test]
+fn load_path_bad_magic() {
+    let path = std::env::temp_dir().join("radiant_load_path_bad_magic.hdr");
+    std::fs::write(&path, b"#!RADIANCE\n\n-Y 1 +X 1\n\xff\x00\xff\x80").unwrap();
+    let result = radiant::load_path(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(matches!(result, Err(LoadError::FileFormat)));
+}
@@ -0,0 +1,37 @@
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+#[test]
+fn load_pixels_matches_load() {
+    let image = radiant::load(HDR).unwrap();
+    let mut count = 0;
+    let dimensions = radiant::load_pixels(HDR, |x, y, pixel| {
+        assert_eq!(pixel, *image.pixel(x, y), "({}, {})", x, y);
+        assert_eq!((x, y), (count % image.width, count / image.width));
+        count += 1;
+    })
+    .unwrap();
+    assert_eq!(dimensions, (image.width, image.height));
+    assert_eq!(count, image.data.len());
+}
+
+#[test]
+fn load_pixels_orientation() {
+    // Columns from right to left, each from bottom to top
+    let bytes = b"#?RADIANCE\n\n-X 2 +Y 2\n\
+        \x01\x00\x00\x80\x02\x00\x00\x80\x03\x00\x00\x80\x04\x00\x00\x80";
+    let image = radiant::load(&bytes[..]).unwrap();
+    let mut order = Vec::new();
+    radiant::load_pixels(&bytes[..], |x, y, pixel| {
+        assert_eq!(pixel, *image.pixel(x, y));
+        order.push((x, y));
+    })
+    .unwrap();
+    assert_eq!(order, [(1, 1), (1, 0), (0, 1), (0, 0)]);
+}
+
+#[test]
+fn load_pixels_empty() {
+    let bytes = b"#?RADIANCE\n\n-Y 0 +X 5\n";
+    let dimensions = radiant::load_pixels(&bytes[..], |_, _, _| panic!()).unwrap();
+    assert_eq!(dimensions, (5, 0));
+}
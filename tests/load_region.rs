@@ -0,0 +1,60 @@
+use radiant::{Image, LoadError, RGB};
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+fn crop(image: &Image, x: usize, y: usize, width: usize, height: usize) -> Vec<RGB> {
+    image
+        .rows()
+        .skip(y)
+        .take(height)
+        .flat_map(|row| &row[x..x + width])
+        .copied()
+        .collect()
+}
+
+#[test]
+fn region_matches_crop() {
+    let full = radiant::load(HDR).unwrap();
+    for &(x, y, width, height) in &[(0, 0, 16, 4), (3, 1, 5, 2), (15, 3, 1, 1), (0, 2, 16, 2)] {
+        let region = radiant::load_region(HDR, x, y, width, height).unwrap();
+        assert_eq!((region.width, region.height), (width, height));
+        assert_eq!(region.data, crop(&full, x, y, width, height));
+    }
+}
+
+#[test]
+fn region_other_orientations() {
+    // A 3x2 image stored as columns from right to left, each from bottom to top
+    let mut bytes = b"#?RADIANCE\n\n-X 3 +Y 2\n".to_vec();
+    for column in (0..3).rev() {
+        for row in (0..2).rev() {
+            bytes.extend_from_slice(&[10 * row + column + 1, 0, 0, 0x80]);
+        }
+    }
+    let full = radiant::load(&bytes[..]).unwrap();
+    let region = radiant::load_region(&bytes[..], 1, 0, 2, 2).unwrap();
+    assert_eq!(region.data, crop(&full, 1, 0, 2, 2));
+    assert_eq!(region.data[0].r, full.pixel(1, 0).r);
+}
+
+#[test]
+fn region_out_of_bounds() {
+    for &(x, y, width, height) in &[
+        (0, 0, 17, 1),
+        (1, 0, 16, 1),
+        (0, 4, 1, 1),
+        (usize::MAX, 0, 2, 1),
+    ] {
+        assert!(matches!(
+            radiant::load_region(HDR, x, y, width, height),
+            Err(LoadError::InvalidRegion)
+        ));
+    }
+}
+
+#[test]
+fn region_empty() {
+    let region = radiant::load_region(HDR, 4, 2, 0, 2).unwrap();
+    assert_eq!((region.width, region.height), (0, 2));
+    assert!(region.data.is_empty());
+}
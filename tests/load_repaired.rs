@@ -0,0 +1,84 @@
+use radiant::{Image, LoadError, RGB};
+
+const WIDTH: usize = 16;
+const HEIGHT: usize = 5;
+
+fn encoded() -> (Image, Vec<u8>) {
+    let mut data = Vec::with_capacity(WIDTH * HEIGHT);
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            data.push(RGB {
+                r: (x / 4) as f32,
+                g: y as f32 + 1.0,
+                b: 0.5,
+            });
+        }
+    }
+    let image = Image::new(WIDTH, HEIGHT, data).unwrap();
+    let mut bytes = Vec::new();
+    radiant::save_rle(&image, &mut bytes).unwrap();
+    let image = radiant::load(&bytes[..]).unwrap();
+    (image, bytes)
+}
+
+/// The offsets of every scanline marker in the data.
+fn markers(bytes: &[u8]) -> Vec<usize> {
+    let marker = [2, 2, 0, WIDTH as u8];
+    (0..bytes.len() - 3)
+        .filter(|&i| bytes[i..i + 4] == marker)
+        .collect()
+}
+
+/// Corrupt the scanline at `index` with a run that is longer than the scanline.
+fn corrupt(bytes: &mut [u8], index: usize) {
+    let start = markers(bytes)[index];
+    bytes[start + 4] = 0xff;
+}
+
+fn assert_rows(image: &Image, full: &Image, damaged: &[usize]) {
+    for (y, (row, full_row)) in image.rows().zip(full.rows()).enumerate() {
+        if damaged.contains(&y) {
+            assert!(row.iter().all(|pixel| pixel.g == 0.0), "row {}", y);
+        } else {
+            assert_eq!(row, full_row, "row {}", y);
+        }
+    }
+}
+
+#[test]
+fn repaired_intact() {
+    let (full, bytes) = encoded();
+    let (image, damaged) = radiant::load_repaired(&bytes[..]).unwrap();
+    assert_eq!(image.data, full.data);
+    assert!(damaged.is_empty());
+}
+
+#[test]
+fn repaired_skips_damaged_scanlines() {
+    let (full, mut bytes) = encoded();
+    corrupt(&mut bytes, 1);
+    corrupt(&mut bytes, 3);
+    assert!(matches!(radiant::load(&bytes[..]), Err(LoadError::Rle)));
+
+    let (image, damaged) = radiant::load_repaired(&bytes[..]).unwrap();
+    assert_eq!(damaged, [1, 3]);
+    assert_rows(&image, &full, &damaged);
+}
+
+#[test]
+fn repaired_last_scanline() {
+    let (full, mut bytes) = encoded();
+    corrupt(&mut bytes, HEIGHT - 1);
+    let (image, damaged) = radiant::load_repaired(&bytes[..]).unwrap();
+    assert_eq!(damaged, [HEIGHT - 1]);
+    assert_rows(&image, &full, &damaged);
+}
+
+#[test]
+fn repaired_old_format_fails() {
+    let bytes = b"#?RADIANCE\n\n-Y 1 +X 2\n\x00\x00\xff\x80\x01\x01\x01\x05";
+    assert!(matches!(
+        radiant::load_repaired(&bytes[..]),
+        Err(LoadError::Rle)
+    ));
+}
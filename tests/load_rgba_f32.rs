@@ -0,0 +1,38 @@
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+#[test]
+fn load_rgba_f32_matches_load() {
+    let (width, height, data) = radiant::load_rgba_f32(HDR, 0.5).unwrap();
+    assert_eq!((width, height), (16, 4));
+    assert_eq!(data.len(), 16 * 4 * 4);
+
+    let image = radiant::load(HDR).unwrap();
+    for (rgba, pixel) in data.chunks_exact(4).zip(&image.data) {
+        assert_eq!(rgba, [pixel.r, pixel.g, pixel.b, 0.5]);
+    }
+}
+
+#[test]
+fn load_rgba_f32_orientation() {
+    // A 2x3 image stored column by column, from the bottom right
+    let mut bytes = b"#?RADIANCE\n\n-X 2 +Y 3\n".to_vec();
+    for x in (0..2).rev() {
+        for y in (0..3).rev() {
+            bytes.extend_from_slice(&[10 * y + x + 1, 0, 0, 0x80]);
+        }
+    }
+
+    let (width, height, data) = radiant::load_rgba_f32(&bytes[..], 1.0).unwrap();
+    assert_eq!((width, height), (2, 3));
+    let image = radiant::load(&bytes[..]).unwrap();
+    let red: Vec<f32> = data.chunks_exact(4).map(|rgba| rgba[0]).collect();
+    let expected: Vec<f32> = image.data.iter().map(|pixel| pixel.r).collect();
+    assert_eq!(red, expected);
+    assert!(data.chunks_exact(4).all(|rgba| rgba[3] == 1.0));
+}
+
+#[test]
+fn load_rgba_f32_error() {
+    let result = radiant::load_rgba_f32(&HDR[..HDR.len() - 1], 1.0);
+    assert!(matches!(result, Err(radiant::LoadError::Eof(_))));
+}
@@ -0,0 +1,87 @@
+use radiant::{Orientation, RGB, RGBE};
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+#[test]
+fn rgbe_flat() {
+    // Flat pixels are stored as is, right after the resolution line
+    let image = radiant::load_rgbe(HDR).unwrap();
+    assert_eq!((image.width, image.height), (16, 4));
+    let stored: Vec<[u8; 4]> = HDR[46..]
+        .chunks_exact(4)
+        .map(|pixel| [pixel[0], pixel[1], pixel[2], pixel[3]])
+        .collect();
+    assert_eq!(image.data, stored);
+}
+
+#[test]
+fn rgbe_matches_load() {
+    let image = radiant::load_rgbe(HDR).unwrap();
+    let full = radiant::load(HDR).unwrap();
+    for (&[r, g, b, e], pixel) in image.data.iter().zip(&full.data) {
+        let scale = 2f32.powi(i32::from(e) - 128) / 255.0;
+        assert_eq!(pixel.r, f32::from(r) * scale);
+        assert_eq!(pixel.g, f32::from(g) * scale);
+        assert_eq!(pixel.b, f32::from(b) * scale);
+    }
+}
+
+#[test]
+fn rgbe_converts_to_rgb() {
+    let image = radiant::load_rgbe(HDR).unwrap();
+    let converted: Vec<RGB> = image
+        .data
+        .iter()
+        .map(|&pixel| RGB::from(RGBE::from(pixel)))
+        .collect();
+    assert_eq!(converted, radiant::load(HDR).unwrap().data);
+
+    let pixel = RGBE::from([1, 2, 3, 4]);
+    assert_eq!((pixel.r, pixel.g, pixel.b, pixel.e), (1, 2, 3, 4));
+    assert_eq!(<[u8; 4]>::from(pixel), [1, 2, 3, 4]);
+}
+
+#[test]
+fn rgbe_run_length_encoded() {
+    // Two rows of 8 pixels with the adaptive encoding, stored from bottom to top
+    let mut bytes = b"#?RADIANCE\n\n+Y 2 +X 8\n".to_vec();
+    for row in 0..2u8 {
+        bytes.extend_from_slice(&[2, 2, 0, 8]);
+        bytes.extend_from_slice(&[0x88, row]);
+        bytes.extend_from_slice(&[8, 1, 2, 3, 4, 5, 6, 7, 8]);
+        bytes.extend_from_slice(&[0x88, 0]);
+        bytes.extend_from_slice(&[0x84, 0x7f, 0x84, 0x90]);
+    }
+    let image = radiant::load_rgbe(&bytes[..]).unwrap();
+    assert_eq!(image.orientation, Orientation::PosYPosX);
+    assert_eq!(image.data[0], [1, 1, 0, 0x7f]);
+    assert_eq!(image.data[7], [1, 8, 0, 0x90]);
+    assert_eq!(image.data[8], [0, 1, 0, 0x7f]);
+}
+
+#[test]
+fn rgbe_named_conversions() {
+    let pixel = RGBE::from_bytes([128, 64, 255, 129]);
+    assert_eq!(pixel.to_bytes(), [128, 64, 255, 129]);
+    assert_eq!(
+        pixel.to_rgb(),
+        RGB {
+            r: 128.0 / 255.0 * 2.0,
+            g: 64.0 / 255.0 * 2.0,
+            b: 2.0,
+        }
+    );
+    assert_eq!(RGBE::from_rgb(pixel.to_rgb()), pixel);
+
+    // The brightest channel picks the exponent
+    let pixel = RGBE::from_rgb(RGB {
+        r: 1.0,
+        g: 0.5,
+        b: 0.0,
+    });
+    assert_eq!(pixel.to_bytes(), [255, 128, 0, 128]);
+
+    assert_eq!(RGBE::from_rgb(RGB::splat(-1.0)).to_bytes(), [0, 0, 0, 0]);
+    assert_eq!(RGBE::from_rgb(RGB::splat(0.0)).to_bytes(), [0, 0, 0, 0]);
+    assert_eq!(RGBE::from_rgb(RGB::splat(f32::MAX)).to_bytes(), [255; 4]);
+}
@@ -0,0 +1,54 @@
+use radiant::LoadError;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{Cursor, Seek, SeekFrom};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Keeps track of the largest allocation made.
+struct LargestAlloc;
+
+static LARGEST: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for LargestAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LARGEST.fetch_max(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: LargestAlloc = LargestAlloc;
+
+#[test]
+fn load_seek_rejects_truncated_huge_image() {
+    let mut bytes = b"#?RADIANCE\n\n-Y 8192 +X 8192\n".to_vec();
+    bytes.resize(2048, 0);
+    let mut reader = Cursor::new(bytes);
+
+    let result = radiant::load_seek(&mut reader);
+    assert!(matches!(result, Err(LoadError::FileFormat)));
+    assert_eq!(reader.position(), 0);
+    assert!(LARGEST.load(Ordering::Relaxed) < 1 << 20);
+}
+
+#[test]
+fn load_seek_matches_load() {
+    let bytes = b"#?RADIANCE\n\n-Y 2 +X 1\n\xff\x00\xff\x80\x00\xff\x00\x80";
+    let image = radiant::load_seek(Cursor::new(&bytes[..])).unwrap();
+    assert_eq!(image.data, radiant::load(&bytes[..]).unwrap().data);
+}
+
+#[test]
+fn load_seek_from_offset() {
+    let mut bytes = b"junk".to_vec();
+    bytes.extend_from_slice(b"#?RADIANCE\n\n-Y 3 +X 1\n\xff\x00\xff\x80\x00\xff\x00\x80");
+    let mut reader = Cursor::new(bytes);
+    reader.seek(SeekFrom::Start(4)).unwrap();
+
+    let result = radiant::load_seek(&mut reader);
+    assert!(matches!(result, Err(LoadError::FileFormat)));
+    assert_eq!(reader.position(), 4);
+}
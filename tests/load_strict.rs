@@ -0,0 +1,61 @@
+use radiant::LoadError;
+
+const IMAGE: &[u8] = b"#?RADIANCE\n\n-Y 1 +X 1\n\xff\x00\xff\x80";
+
+fn strict(trailing: &[u8]) -> Result<radiant::Image, LoadError> {
+    let mut bytes = IMAGE.to_vec();
+    bytes.extend_from_slice(trailing);
+    radiant::load_strict(&bytes[..])
+}
+
+#[test]
+fn strict_exact() {
+    let image = strict(b"").unwrap();
+    assert_eq!(image.data, radiant::load(IMAGE).unwrap().data);
+}
+
+#[test]
+fn strict_allows_newline() {
+    assert!(strict(b"\n").is_ok());
+}
+
+#[test]
+fn strict_rejects_trailing_data() {
+    for (trailing, expected) in [(&b"\n\n"[..], 2), (b"x", 1), (b"\x00\x00\x00\x00\x00", 5)] {
+        match strict(trailing) {
+            Err(LoadError::TrailingData { extra_bytes }) => assert_eq!(extra_bytes, expected),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn strict_counts_across_buffers() {
+    let mut bytes = IMAGE.to_vec();
+    bytes.extend_from_slice(&[b'\n'; 100]);
+    let reader = std::io::BufReader::with_capacity(7, &bytes[..]);
+    assert!(matches!(
+        radiant::load_strict(reader),
+        Err(LoadError::TrailingData { extra_bytes: 100 })
+    ));
+}
+
+#[test]
+fn load_ignores_trailing_data() {
+    assert!(radiant::load(&b"#?RADIANCE\n\n-Y 1 +X 1\n\xff\x00\xff\x80junk"[..]).is_ok());
+}
+
+#[test]
+fn extreme_exponents_are_finite() {
+    // The largest exponent scales the largest mantissa to 2^127, which f32 can still hold
+    let mut bytes = b"#?RADIANCE\n\n-Y 16 +X 16\n".to_vec();
+    for e in 0..=255 {
+        bytes.extend_from_slice(&[255, 255, 255, e]);
+    }
+    let image = radiant::load_strict(&bytes[..]).unwrap();
+    assert!(image
+        .data
+        .iter()
+        .all(|pixel| pixel.r.is_finite() && pixel.g.is_finite() && pixel.b.is_finite()));
+    assert_eq!(image.data[255].r, 2_f32.powi(127));
+}
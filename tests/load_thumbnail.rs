@@ -0,0 +1,87 @@
+use radiant::{Image, RGB};
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+fn energy(data: &[RGB], weights: impl Iterator<Item = f32>) -> f32 {
+    data.iter()
+        .zip(weights)
+        .map(|(pixel, weight)| (pixel.r + pixel.g + pixel.b) * weight)
+        .sum()
+}
+
+/// Average each block of `factor` by `factor` pixels of a fully decoded image.
+fn box_downsample(image: &Image, factor: usize) -> Vec<RGB> {
+    let width = image.width.div_ceil(factor);
+    let height = image.height.div_ceil(factor);
+    let mut data = Vec::new();
+    for ty in 0..height {
+        for tx in 0..width {
+            let mut sum = RGB::default();
+            let mut count = 0.0;
+            for y in ty * factor..((ty + 1) * factor).min(image.height) {
+                for x in tx * factor..((tx + 1) * factor).min(image.width) {
+                    sum = sum + *image.pixel(x, y);
+                    count += 1.0;
+                }
+            }
+            data.push(sum / count);
+        }
+    }
+    data
+}
+
+#[test]
+fn thumbnail_size() {
+    // The gradient is 16x4
+    for &(max_dim, width, height) in &[
+        (16, 16, 4),
+        (100, 16, 4),
+        (8, 8, 2),
+        (5, 4, 1),
+        (3, 3, 1),
+        (1, 1, 1),
+        (0, 1, 1),
+    ] {
+        let image = radiant::load_thumbnail(HDR, max_dim).unwrap();
+        assert_eq!(
+            (image.width, image.height),
+            (width, height),
+            "max_dim {}",
+            max_dim
+        );
+    }
+}
+
+#[test]
+fn thumbnail_matches_box_downsample() {
+    let full = radiant::load(HDR).unwrap();
+    for &(max_dim, factor) in &[(16, 1), (8, 2), (5, 4), (3, 6), (1, 16)] {
+        let image = radiant::load_thumbnail(HDR, max_dim).unwrap();
+        let expected = box_downsample(&full, factor);
+        assert_eq!(image.data.len(), expected.len());
+        for (a, b) in image.data.iter().zip(&expected) {
+            assert!((a.r - b.r).abs() < 1e-4, "{:?} != {:?}", a, b);
+            assert!((a.g - b.g).abs() < 1e-4, "{:?} != {:?}", a, b);
+            assert!((a.b - b.b).abs() < 1e-4, "{:?} != {:?}", a, b);
+        }
+    }
+}
+
+#[test]
+fn thumbnail_keeps_energy() {
+    // A single bright pixel in an otherwise dark 9x9 image survives being scaled down
+    let mut bytes = b"#?RADIANCE\n\n-Y 9 +X 9\n".to_vec();
+    for i in 0..81 {
+        let e = if i == 40 { 0x90 } else { 0x80 };
+        bytes.extend_from_slice(&[0x10, 0x10, 0x10, e]);
+    }
+    let full = radiant::load(&bytes[..]).unwrap();
+    let image = radiant::load_thumbnail(&bytes[..], 2).unwrap();
+    assert_eq!((image.width, image.height), (2, 2));
+
+    // Each thumbnail pixel stands for a 5x5, 5x4, 4x5 or 4x4 block
+    let total = energy(&full.data, std::iter::repeat(1.0));
+    let thumbnail = energy(&image.data, [25.0, 20.0, 20.0, 16.0].iter().copied());
+    assert!((total - thumbnail).abs() / total < 1e-4);
+    assert!(image.data[0].r > 100.0);
+}
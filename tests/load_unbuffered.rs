@@ -0,0 +1,48 @@
+use radiant::LoadError;
+use std::io::Read;
+
+const IMAGE: &[u8] = b"#?RADIANCE\0\n\n-Y 2 +X 8\n\
+    \x02\x02\x00\x08\
+    \x88\xff\x88\x00\x88\xff\x88\x80\
+    \xff\x00\xff\x80\x01\x01\x01\x07";
+
+/// A reader that hands out a single byte per call.
+struct OneByte<R>(R);
+
+impl<R: Read> Read for OneByte<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = buf.len().min(1);
+        self.0.read(&mut buf[..len])
+    }
+}
+
+#[test]
+fn load_unbuffered_one_byte_at_a_time() {
+    let image = radiant::load_unbuffered(OneByte(IMAGE)).unwrap();
+    let expected = radiant::load(IMAGE).unwrap();
+    assert_eq!(image.width, expected.width);
+    assert_eq!(image.height, expected.height);
+    assert_eq!(image.data, expected.data);
+}
+
+#[test]
+fn load_unbuffered_truncated() {
+    for len in 0..IMAGE.len() {
+        let result = radiant::load_unbuffered(OneByte(&IMAGE[..len]));
+        assert_eq!(
+            format!("{:?}", result),
+            format!("{:?}", radiant::load(&IMAGE[..len])),
+            "mismatch when truncated to {} bytes",
+            len
+        );
+    }
+}
+
+#[test]
+fn load_unbuffered_boxed() {
+    let reader: Box<dyn Read> = Box::new(OneByte(&b"#?RADIANCE\0\n\n-Y 1 +X 1\n"[..]));
+    assert!(matches!(
+        radiant::load_unbuffered(reader),
+        Err(LoadError::Eof(_))
+    ));
+}
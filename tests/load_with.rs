@@ -0,0 +1,52 @@
+use radiant::{FromRgbe, RGB};
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+/// An engine's own pixel type, with alpha.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LinearRgb([f32; 4]);
+
+impl FromRgbe for LinearRgb {
+    fn from_rgbe(r: u8, g: u8, b: u8, e: u8) -> Self {
+        let [r, g, b] = <[f32; 3]>::from_rgbe(r, g, b, e);
+        LinearRgb([r, g, b, 0.5])
+    }
+}
+
+#[test]
+fn load_with_rgb_matches_load() {
+    let (width, height, data) = radiant::load_with::<RGB, _>(HDR).unwrap();
+    assert_eq!((width, height), (16, 4));
+    assert_eq!(data, radiant::load(HDR).unwrap().data);
+}
+
+#[test]
+fn load_with_arrays() {
+    let full = radiant::load(HDR).unwrap();
+    let (_, _, rgb) = radiant::load_with::<[f32; 3], _>(HDR).unwrap();
+    let (_, _, rgba) = radiant::load_with::<[f32; 4], _>(HDR).unwrap();
+    for ((pixel, rgb), rgba) in full.data.iter().zip(rgb).zip(rgba) {
+        assert_eq!(rgb, [pixel.r, pixel.g, pixel.b]);
+        assert_eq!(rgba, [pixel.r, pixel.g, pixel.b, 1.0]);
+    }
+}
+
+#[test]
+fn load_with_custom_pixel() {
+    let full = radiant::load(HDR).unwrap();
+    let (_, _, data) = radiant::load_with::<LinearRgb, _>(HDR).unwrap();
+    for (pixel, custom) in full.data.iter().zip(data) {
+        assert_eq!(custom, LinearRgb([pixel.r, pixel.g, pixel.b, 0.5]));
+    }
+}
+
+#[test]
+fn load_with_run_length_encoded() {
+    // One row of 8 pixels with the adaptive encoding
+    let mut bytes = b"#?RADIANCE\n\n-Y 1 +X 8\n\x02\x02\x00\x08".to_vec();
+    bytes.extend_from_slice(&[0x88, 0x80, 0x88, 0x40, 0x88, 0, 0x88, 0x81]);
+    let (_, _, data) = radiant::load_with::<RGB, _>(&bytes[..]).unwrap();
+    assert_eq!(data, radiant::load(&bytes[..]).unwrap().data);
+    assert_eq!(data[0], data[7]);
+}
@@ -0,0 +1,27 @@
+use radiant::DecodeProgress;
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+#[test]
+fn progress_per_scanline() {
+    let mut reports = Vec::new();
+    let image = radiant::load_with_progress(HDR, |progress| reports.push(progress)).unwrap();
+    assert_eq!(image.data, radiant::load(HDR).unwrap().data);
+
+    let expected: Vec<_> = (1..=4)
+        .map(|rows_done| DecodeProgress {
+            rows_done,
+            rows_total: 4,
+            bytes_read: 46 + 64 * rows_done as u64,
+        })
+        .collect();
+    assert_eq!(reports, expected);
+}
+
+#[test]
+fn progress_empty_image() {
+    let mut calls = 0;
+    let reader = b"#?RADIANCE\n\n-Y 0 +X 3\n";
+    radiant::load_with_progress(&reader[..], |_| calls += 1).unwrap();
+    assert_eq!(calls, 0);
+}
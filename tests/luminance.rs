@@ -0,0 +1,27 @@
+use radiant::RGB;
+
+#[test]
+fn luminance_weights() {
+    let pixel = RGB {
+        r: 1.0,
+        g: 2.0,
+        b: 4.0,
+    };
+    assert!((pixel.luminance() - (0.265 + 1.34 + 0.26)).abs() < 1e-6);
+}
+
+#[test]
+fn luminance_range() {
+    let reader = b"#?RADIANCE\n\n-Y 1 +X 3\n\xff\x00\x00\x80\x00\xff\x00\x81\x00\x00\xff\x7f";
+    let image = radiant::load(&reader[..]).unwrap();
+    let (min, max) = image.luminance_range().unwrap();
+    assert_eq!(min, image.data[2].luminance());
+    assert_eq!(max, image.data[1].luminance());
+}
+
+#[test]
+fn luminance_range_empty() {
+    let reader = b"#?RADIANCE\n\n-Y 0 +X 3\n";
+    let image = radiant::load(&reader[..]).unwrap();
+    assert_eq!(image.luminance_range(), None);
+}
@@ -0,0 +1,60 @@
+use radiant::{LoadError, PushDecoder};
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+const HDR_RGBE: &[u8] = include_bytes!("../assets/gradient_rgbe.hdr");
+
+fn with_first_line(line: &str) -> Vec<u8> {
+    let mut bytes = format!("{}\n\n-Y 1 +X 1\n", line).into_bytes();
+    bytes.extend_from_slice(b"\xff\x00\xff\x80");
+    bytes
+}
+
+#[test]
+fn magic_rgbe_variant() {
+    let (image, header) = radiant::load_with_header(HDR_RGBE).unwrap();
+    assert_eq!(header.program.as_deref(), Some("RGBE"));
+    assert_eq!(image.data, radiant::load(HDR).unwrap().data);
+
+    let mut decoder = PushDecoder::new();
+    decoder.feed(HDR_RGBE).unwrap();
+    assert_eq!(decoder.finish().unwrap().data, image.data);
+
+    let (image, _) = radiant::load_lenient(HDR_RGBE).unwrap();
+    assert_eq!(image.data, radiant::load(HDR).unwrap().data);
+}
+
+#[test]
+fn magic_program() {
+    let info = radiant::read_header(HDR).unwrap();
+    assert_eq!(info.header.program.as_deref(), Some("RADIANCE"));
+
+    let bytes = with_first_line("#?RADIANCE\0 \r");
+    let info = radiant::read_header(&bytes[..]).unwrap();
+    assert_eq!(info.header.program.as_deref(), Some("RADIANCE"));
+}
+
+#[test]
+fn magic_other_program() {
+    for (line, program) in [
+        ("#?RADIANCEX", "RADIANCEX"),
+        ("#?", ""),
+        ("#?PICTURE", "PICTURE"),
+        ("#? RADIANCE", " RADIANCE"),
+        ("#?rgbe", "rgbe"),
+    ] {
+        let bytes = with_first_line(line);
+        let (image, header) = radiant::load_with_header(&bytes[..]).unwrap();
+        assert_eq!(header.program.as_deref(), Some(program));
+        assert_eq!(image.data, radiant::load(&bytes[..]).unwrap().data);
+        assert!(radiant::load_lenient(&bytes[..]).is_ok());
+        assert!(PushDecoder::new().feed(&bytes).is_ok());
+
+        // Only the strict loader insists on a known program
+        assert!(
+            matches!(radiant::load_strict(&bytes[..]), Err(LoadError::FileFormat)),
+            "{}",
+            line
+        );
+    }
+    assert!(radiant::load_strict(&with_first_line("#?RGBE")[..]).is_ok());
+}
@@ -0,0 +1,27 @@
+use radiant::RGB;
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+#[test]
+fn map_new_image() {
+    let image = radiant::load(HDR).unwrap();
+    let doubled = image.map(|pixel| pixel * 2.0);
+    assert_eq!((doubled.width, doubled.height), (image.width, image.height));
+    assert_eq!(doubled.orientation, image.orientation);
+    for (a, b) in doubled.data.iter().zip(&image.data) {
+        assert_eq!(*a, *b * 2.0);
+    }
+}
+
+#[test]
+fn map_in_place_order() {
+    let mut image = radiant::load(HDR).unwrap();
+    let mut index = 0.0;
+    image.map_in_place(|_| {
+        index += 1.0;
+        RGB::splat(index)
+    });
+    let values: Vec<f32> = image.data.iter().map(|pixel| pixel.g).collect();
+    let expected: Vec<f32> = (1..=image.data.len()).map(|i| i as f32).collect();
+    assert_eq!(values, expected);
+}
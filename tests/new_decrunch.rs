@@ -4,7 +4,7 @@ use std::io::Read;
 #[test]
 fn new_decrunch_rle() {
     let reader = b"#?RADIANCE\0\n\n-Y 1 +X 8\n\
-        \x02\x02\x08\x00\
+        \x02\x02\x00\x08\
         \x88\xff\x88\x00\x88\xff\x88\x80";
     let image = radiant::load(&reader[..]).unwrap();
     assert_eq!(image.width, 8);
@@ -22,7 +22,7 @@ fn new_decrunch_rle() {
 #[test]
 fn new_decrunch_zero_length_run() {
     let reader = b"#?RADIANCE\0\n\n-Y 1 +X 8\n\
-        \x02\x02\x08\x00\
+        \x02\x02\x00\x08\
         \x88\xff\x88\x00\x88\xff\x88\x80\x80\x56";
     let image = radiant::load(&reader[..]).unwrap();
     assert_eq!(image.width, 8);
@@ -40,9 +40,38 @@ fn new_decrunch_zero_length_run() {
 #[test]
 fn new_decrunch_ignore_rest() {
     let reader = b"#?RADIANCE\0\n\n-Y 1 +X 8\n\
-        \x02\x02\x08\x00\
+        \x02\x02\x00\x08\
         \x88\xff\x88\x00\x88\xff\x88\x80";
     let mut reader = reader.chain(&reader[..]);
     radiant::load(&mut reader).unwrap();
     radiant::load(&mut reader).unwrap();
 }
+
+#[test]
+fn new_decrunch_length_mismatch() {
+    // The marker claims 9 pixels but the scanline has 8, which used to shift every channel
+    let reader = b"#?RADIANCE\n\n-Y 1 +X 8\n\
+        \x02\x02\x00\x09\
+        \x88\xff\x88\x00\x88\xff\x88\x80\x01\x00";
+    assert!(matches!(
+        radiant::load(&reader[..]),
+        Err(radiant::LoadError::Rle)
+    ));
+    assert!(matches!(
+        radiant::PushDecoder::new().feed(&reader[..]),
+        Err(radiant::LoadError::Rle)
+    ));
+    assert!(matches!(
+        radiant::validate(&reader[..]),
+        Err(radiant::LoadError::Rle)
+    ));
+}
+
+#[test]
+fn new_decrunch_marker_too_short_for_new_format() {
+    // Scanlines shorter than 8 pixels never use the new format, so this is a plain pixel
+    let reader = b"#?RADIANCE\n\n-Y 1 +X 2\n\x02\x02\x00\x81\x02\x02\x00\x81";
+    let image = radiant::load(&reader[..]).unwrap();
+    assert_eq!(image.data[0], image.data[1]);
+    assert_eq!(image.data[0].r, 4.0 / 255.0);
+}
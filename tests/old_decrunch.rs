@@ -89,3 +89,150 @@ fn old_decrunch_zero_length_run() {
         },]
     );
 }
+
+#[test]
+fn old_decrunch_one_pixel_wide() {
+    let reader = b"#?RADIANCE\n\n-Y 3 +X 1\n\xff\x00\x00\x80\x00\xff\x00\x80\x00\x00\xff\x80";
+    let (image, consumed) = radiant::load_counted(&reader[..]).unwrap();
+    assert_eq!((image.width, image.height), (1, 3));
+    assert_eq!(consumed, reader.len() as u64);
+    assert_eq!(
+        &image.data,
+        &[
+            RGB {
+                r: 1.0,
+                g: 0.0,
+                b: 0.0,
+            },
+            RGB {
+                r: 0.0,
+                g: 1.0,
+                b: 0.0,
+            },
+            RGB {
+                r: 0.0,
+                g: 0.0,
+                b: 1.0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn old_decrunch_one_pixel_tall() {
+    let reader = b"#?RADIANCE\n\n-Y 1 +X 3\n\xff\x00\x00\x80\x00\xff\x00\x80\x00\x00\xff\x80";
+    let (image, consumed) = radiant::load_counted(&reader[..]).unwrap();
+    assert_eq!((image.width, image.height), (3, 1));
+    assert_eq!(consumed, reader.len() as u64);
+    assert_eq!(
+        &image.data,
+        &[
+            RGB {
+                r: 1.0,
+                g: 0.0,
+                b: 0.0,
+            },
+            RGB {
+                r: 0.0,
+                g: 1.0,
+                b: 0.0,
+            },
+            RGB {
+                r: 0.0,
+                g: 0.0,
+                b: 1.0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn old_decrunch_run_to_last_pixel() {
+    let reader = b"#?RADIANCE\n\n-Y 1 +X 3\n\xff\x00\xff\x80\x01\x01\x01\x02";
+    let image = radiant::load(&reader[..]).unwrap();
+    assert_eq!(
+        image.data,
+        [RGB {
+            r: 1.0,
+            g: 0.0,
+            b: 1.0,
+        }; 3]
+    );
+}
+
+#[test]
+fn old_decrunch_zero_width() {
+    let reader = b"#?RADIANCE\n\n-Y 2 +X 0\n";
+    let image = radiant::load(&reader[..]).unwrap();
+    assert_eq!((image.width, image.height), (0, 2));
+    assert!(image.data.is_empty());
+}
+
+/// A run that continues into the next scanline, and one at the start of a scanline that repeats
+/// the last pixel of the one before.
+const ACROSS_SCANLINES: &[u8] = b"#?RADIANCE\n\n-Y 3 +X 2\n\
+    \xff\x00\x00\x80\x01\x01\x01\x02\
+    \x00\x00\xff\x80\x01\x01\x01\x02";
+
+fn across_scanlines_data() -> Vec<RGB> {
+    let red = RGB {
+        r: 1.0,
+        g: 0.0,
+        b: 0.0,
+    };
+    let blue = RGB {
+        r: 0.0,
+        g: 0.0,
+        b: 1.0,
+    };
+    vec![red, red, red, blue, blue, blue]
+}
+
+#[test]
+fn old_decrunch_run_across_scanlines() {
+    let (image, consumed) = radiant::load_counted(ACROSS_SCANLINES).unwrap();
+    assert_eq!(consumed, ACROSS_SCANLINES.len() as u64);
+    assert_eq!(image.data, across_scanlines_data());
+
+    let mut data = Vec::new();
+    radiant::for_each_scanline(ACROSS_SCANLINES, |_, scanline| {
+        data.extend_from_slice(scanline);
+        std::ops::ControlFlow::Continue(())
+    })
+    .unwrap();
+    assert_eq!(data, across_scanlines_data());
+}
+
+#[test]
+fn old_decrunch_run_across_scanlines_pushed() {
+    let mut decoder = radiant::PushDecoder::new();
+    for chunk in ACROSS_SCANLINES.chunks(1) {
+        decoder.feed(chunk).unwrap();
+    }
+    assert_eq!(decoder.finish().unwrap().data, across_scanlines_data());
+}
+
+#[test]
+fn old_decrunch_run_across_scanlines_skipped() {
+    let mut decoder = radiant::Decoder::new(ACROSS_SCANLINES).unwrap();
+    decoder.skip_scanlines(1).unwrap();
+    let mut scanline = [RGB::default(); 2];
+    decoder.read_scanline(&mut scanline).unwrap();
+    assert_eq!(scanline[..], across_scanlines_data()[2..4]);
+    decoder.skip_scanlines(1).unwrap();
+}
+
+#[test]
+fn old_decrunch_run_past_end() {
+    let reader = b"#?RADIANCE\n\n-Y 2 +X 2\n\xff\x00\x00\x80\x01\x01\x01\x04";
+    assert!(matches!(
+        radiant::load(&reader[..]),
+        Err(radiant::LoadError::Rle)
+    ));
+
+    let mut decoder = radiant::PushDecoder::new();
+    assert!(matches!(
+        decoder.feed(&reader[..]),
+        Err(radiant::LoadError::Rle)
+    ));
+}
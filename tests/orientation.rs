@@ -0,0 +1,101 @@
+use radiant::{LoadError, Orientation};
+
+const WIDTH: usize = 3;
+const HEIGHT: usize = 2;
+
+/// The red byte stored for the pixel at `(x, y)`, counting from the top left.
+fn red(x: usize, y: usize) -> u8 {
+    (10 * y + x + 1) as u8
+}
+
+/// Build a file storing a 3x2 image with flat scanlines in the order given by `resolution`.
+fn file(resolution: &str, x_major: bool, flip_x: bool, flip_y: bool) -> Vec<u8> {
+    let mut bytes = format!("#?RADIANCE\n\n{}\n", resolution).into_bytes();
+    let xs = |i: usize| if flip_x { WIDTH - 1 - i } else { i };
+    let ys = |i: usize| if flip_y { HEIGHT - 1 - i } else { i };
+    if x_major {
+        for s in 0..WIDTH {
+            for i in 0..HEIGHT {
+                bytes.extend_from_slice(&[red(xs(s), ys(i)), 0, 0, 0x80]);
+            }
+        }
+    } else {
+        for s in 0..HEIGHT {
+            for i in 0..WIDTH {
+                bytes.extend_from_slice(&[red(xs(i), ys(s)), 0, 0, 0x80]);
+            }
+        }
+    }
+    bytes
+}
+
+fn check(resolution: &str, orientation: Orientation, flip_x: bool, flip_y: bool) {
+    let bytes = file(resolution, orientation.is_x_major(), flip_x, flip_y);
+    let image = radiant::load(&bytes[..]).unwrap();
+    assert_eq!(image.width, WIDTH, "{}", resolution);
+    assert_eq!(image.height, HEIGHT, "{}", resolution);
+    assert_eq!(image.orientation, orientation, "{}", resolution);
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            assert_eq!(
+                image.pixel(x, y).r,
+                red(x, y) as f32 * (1.0 / 255.0),
+                "{} at ({}, {})",
+                resolution,
+                x,
+                y
+            );
+        }
+    }
+}
+
+#[test]
+fn orientation_standard() {
+    check("-Y 2 +X 3", Orientation::NegYPosX, false, false);
+}
+
+#[test]
+fn orientation_flipped() {
+    check("+Y 2 +X 3", Orientation::PosYPosX, false, true);
+    check("-Y 2 -X 3", Orientation::NegYNegX, true, false);
+    check("+Y 2 -X 3", Orientation::PosYNegX, true, true);
+}
+
+#[test]
+fn orientation_x_major() {
+    check("+X 3 -Y 2", Orientation::PosXNegY, false, false);
+    check("+X 3 +Y 2", Orientation::PosXPosY, false, true);
+    check("-X 3 -Y 2", Orientation::NegXNegY, true, false);
+    check("-X 3 +Y 2", Orientation::NegXPosY, true, true);
+}
+
+#[test]
+fn orientation_x_major_large() {
+    for &(width, height) in &[(7, 5), (5, 7), (6, 6), (1, 9), (9, 1)] {
+        let mut bytes = format!("#?RADIANCE\n\n-X {} +Y {}\n", width, height).into_bytes();
+        for s in 0..width {
+            for i in 0..height {
+                let (x, y) = (width - 1 - s, height - 1 - i);
+                bytes.extend_from_slice(&[(x + 1) as u8, (y + 1) as u8, 0, 0x80]);
+            }
+        }
+
+        let image = radiant::load(&bytes[..]).unwrap();
+        for y in 0..height {
+            for x in 0..width {
+                let pixel = image.pixel(x, y);
+                assert_eq!(pixel.r, (x + 1) as f32 * (1.0 / 255.0));
+                assert_eq!(pixel.g, (y + 1) as f32 * (1.0 / 255.0));
+            }
+        }
+    }
+}
+
+#[test]
+fn orientation_same_axis_twice() {
+    let reader = b"#?RADIANCE\n\n-Y 1 +Y 1\n\xff\x00\xff\x80";
+    assert!(matches!(
+        radiant::load(&reader[..]),
+        Err(LoadError::InvalidDimensions(line)) if line == "-Y 1 +Y 1"
+    ));
+}
@@ -0,0 +1,116 @@
+use radiant::{Header, Image, LoadError, Warning, RGB};
+
+fn with_pixel_aspect(value: &str) -> Vec<u8> {
+    let mut bytes = format!("#?RADIANCE\nPIXASPECT={}\n\n-Y 1 +X 1\n", value).into_bytes();
+    bytes.extend_from_slice(b"\x80\x40\xff\x81");
+    bytes
+}
+
+/// A 2x2 image with a different brightness in each corner.
+fn corners() -> Image {
+    Image::new(
+        2,
+        2,
+        [0.0, 1.0, 2.0, 3.0]
+            .iter()
+            .map(|&v| RGB::splat(v))
+            .collect(),
+    )
+    .unwrap()
+}
+
+fn aspect(pixel_aspect: f32) -> Image {
+    let mut image = corners();
+    image.set_pixel_aspect(pixel_aspect);
+    image
+}
+
+#[test]
+fn pixel_aspect_product() {
+    let bytes = b"#?RADIANCE\nPIXASPECT=2\nPIXASPECT= 0.75\n\n-Y 1 +X 1\n\x80\x40\xff\x81";
+    let (_, header) = radiant::load_with_header(&bytes[..]).unwrap();
+    assert_eq!(header.pixel_aspect, Some(1.5));
+    assert_eq!(header.display_aspect_ratio(300, 100), 2.0);
+    assert_eq!(Header::default().display_aspect_ratio(300, 100), 3.0);
+}
+
+#[test]
+fn resample_tall_pixels() {
+    let image = aspect(2.0).resample_to_square_pixels();
+    assert_eq!((image.width, image.height), (2, 4));
+    let column: Vec<f32> = (0..4).map(|y| image.pixel(0, y).r).collect();
+    assert_eq!(column, [0.0, 0.5, 1.5, 2.0]);
+    let column: Vec<f32> = (0..4).map(|y| image.pixel(1, y).r).collect();
+    assert_eq!(column, [1.0, 1.5, 2.5, 3.0]);
+}
+
+#[test]
+fn resample_wide_pixels() {
+    let image = aspect(0.5).resample_to_square_pixels();
+    assert_eq!((image.width, image.height), (4, 2));
+    let row: Vec<f32> = (0..4).map(|x| image.pixel(x, 1).r).collect();
+    assert_eq!(row, [2.0, 2.25, 2.75, 3.0]);
+}
+
+#[test]
+fn resample_square_pixels() {
+    for image in [corners(), aspect(1.0)] {
+        let resampled = image.resample_to_square_pixels();
+        assert_eq!((resampled.width, resampled.height), (2, 2));
+        assert_eq!(resampled.data, image.data);
+    }
+}
+
+#[test]
+fn pixel_aspect_invalid_values() {
+    for value in ["0", "-1", "wide"] {
+        let bytes = with_pixel_aspect(value);
+
+        let (_, header) = radiant::load_with_header(&bytes[..]).unwrap();
+        assert_eq!(header.pixel_aspect, None, "{}", value);
+
+        let (_, warnings) = radiant::load_lenient(&bytes[..]).unwrap();
+        assert_eq!(warnings, [Warning::InvalidPixelAspect(value.to_owned())]);
+
+        assert!(matches!(
+            radiant::load_strict(&bytes[..]),
+            Err(LoadError::FileFormat)
+        ));
+    }
+    assert!(radiant::load_strict(&with_pixel_aspect("0.5")[..]).is_ok());
+}
+
+#[test]
+fn image_pixel_aspect() {
+    let bytes = b"#?RADIANCE\nPIXASPECT=2\nPIXASPECT= 0.75\n\n-Y 1 +X 1\n\x80\x40\xff\x81";
+    assert_eq!(radiant::load(&bytes[..]).unwrap().pixel_aspect(), 1.5);
+    let (image, _) = radiant::load_with_header(&bytes[..]).unwrap();
+    assert_eq!(image.pixel_aspect(), 1.5);
+    assert_eq!(
+        radiant::load(&with_pixel_aspect("wide")[..])
+            .unwrap()
+            .pixel_aspect(),
+        1.0
+    );
+
+    let mut decoder = radiant::PushDecoder::new();
+    decoder.feed(&bytes[..]).unwrap();
+    assert_eq!(decoder.finish().unwrap().pixel_aspect(), 1.5);
+
+    // Saving keeps it, in place of the lines in the header
+    let (_, header) = radiant::load_with_header(&bytes[..]).unwrap();
+    let mut saved = Vec::new();
+    radiant::save_with_header(&image, &header, &mut saved).unwrap();
+    let (resaved, header) = radiant::load_with_header(&saved[..]).unwrap();
+    assert_eq!(resaved.pixel_aspect(), 1.5);
+    assert_eq!(header.pixel_aspect, Some(1.5));
+
+    // Downsampling keeps it, and resampling uses the aspect of the image
+    let small = image.downsample_box(std::num::NonZeroUsize::new(1).unwrap());
+    assert_eq!(small.pixel_aspect(), 1.5);
+    let square = small.resample_to_square_pixels();
+    assert_eq!((square.width, square.height), (1, 2));
+    assert_eq!(square.pixel_aspect(), 1.0);
+    let saved = radiant::encode(&square).unwrap();
+    assert!(!String::from_utf8_lossy(&saved).contains("PIXASPECT"));
+}
@@ -0,0 +1,96 @@
+use radiant::{LoadError, PushDecoder};
+
+const IMAGE: &[u8] = b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y 3 +X 8\n\
+    \x02\x02\x00\x08\
+    \x84\x10\x04\x20\x21\x22\x23\x88\x00\x88\x81\x88\x80\
+    \xff\x00\xff\x80\x01\x01\x01\x07\
+    \x02\x02\x00\x08\
+    \x88\xff\x00\x88\xff\x02\x01\x02\x86\x03\x88\x80";
+
+#[test]
+fn push_decoder_matches_load() {
+    let expected = radiant::load(IMAGE).unwrap();
+    for chunk_size in 1..=IMAGE.len() {
+        let mut decoder = PushDecoder::new();
+        for chunk in IMAGE.chunks(chunk_size) {
+            let progress = decoder.feed(chunk).unwrap();
+            assert_eq!(progress.consumed, chunk.len());
+        }
+        let image = decoder.finish().unwrap();
+        assert_eq!(image.width, expected.width);
+        assert_eq!(image.height, expected.height);
+        assert_eq!(image.data, expected.data, "chunk size {}", chunk_size);
+    }
+}
+
+#[test]
+fn push_decoder_split_marker() {
+    let header_len = IMAGE.iter().rposition(|&b| b == b'\n').unwrap() + 1;
+    let mut decoder = PushDecoder::new();
+    let progress = decoder.feed(&IMAGE[..header_len + 2]).unwrap();
+    assert!(progress.header_complete);
+    assert!(!progress.complete);
+    assert_eq!(decoder.dimensions(), Some((8, 3)));
+
+    let progress = decoder.feed(&IMAGE[header_len + 2..]).unwrap();
+    assert!(progress.complete);
+    assert_eq!(
+        decoder.finish().unwrap().data,
+        radiant::load(IMAGE).unwrap().data
+    );
+}
+
+#[test]
+fn push_decoder_leaves_trailing_bytes() {
+    let mut bytes = IMAGE.to_vec();
+    bytes.extend_from_slice(b"trailing");
+    let mut decoder = PushDecoder::new();
+    let progress = decoder.feed(&bytes).unwrap();
+    assert_eq!(progress.consumed, IMAGE.len());
+    assert!(progress.complete);
+
+    let progress = decoder.feed(b"more").unwrap();
+    assert_eq!(progress.consumed, 0);
+    decoder.finish().unwrap();
+}
+
+#[test]
+fn push_decoder_truncated() {
+    let mut decoder = PushDecoder::new();
+    let progress = decoder.feed(&IMAGE[..IMAGE.len() - 1]).unwrap();
+    assert!(!progress.complete);
+    assert!(matches!(decoder.finish(), Err(LoadError::Eof(_))));
+}
+
+#[test]
+fn push_decoder_bad_magic() {
+    let mut decoder = PushDecoder::new();
+    assert!(matches!(
+        decoder.feed(b"#!RADIANCE\n\n-Y 1 +X 1\n"),
+        Err(LoadError::FileFormat)
+    ));
+}
+
+#[test]
+fn push_decoder_gzip() {
+    let mut decoder = PushDecoder::new();
+    decoder.feed(b"\x1f").unwrap();
+    assert!(matches!(
+        decoder.feed(b"\x8b\x08\x00"),
+        Err(LoadError::Gzip)
+    ));
+}
+
+#[test]
+fn push_decoder_x_major() {
+    let bytes = b"#?RADIANCE\n\n+X 2 +Y 3\n\
+        \x01\x00\x00\x80\x02\x00\x00\x80\x03\x00\x00\x80\
+        \x04\x00\x00\x80\x05\x00\x00\x80\x06\x00\x00\x80";
+    let mut decoder = PushDecoder::new();
+    decoder.feed(bytes).unwrap();
+    let image = decoder.finish().unwrap();
+    let expected = radiant::load(&bytes[..]).unwrap();
+    assert_eq!((image.width, image.height), (2, 3));
+    assert_eq!(image.orientation, expected.orientation);
+    assert_eq!(image.data, expected.data);
+}
@@ -0,0 +1,36 @@
+use radiant::{ColorFormat, LoadError, Orientation};
+
+const IMAGE: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+#[test]
+fn read_header_matches_load() {
+    let info = radiant::read_header(IMAGE).unwrap();
+    let image = radiant::load(IMAGE).unwrap();
+    assert_eq!((info.width, info.height), (image.width, image.height));
+    assert_eq!(info.orientation, Orientation::NegYPosX);
+    assert_eq!(info.header.format, Some(ColorFormat::Rgbe));
+}
+
+#[test]
+fn read_header_truncated_after_header() {
+    let header_len = IMAGE.windows(2).position(|w| w == b"\n\n").unwrap() + 2;
+    let resolution_len = IMAGE[header_len..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .unwrap()
+        + 1;
+    let truncated = &IMAGE[..header_len + resolution_len];
+
+    let info = radiant::read_header(truncated).unwrap();
+    assert_eq!((info.width, info.height), (16, 4));
+    assert!(matches!(radiant::load(truncated), Err(LoadError::Eof(_))));
+}
+
+#[test]
+fn read_header_bad_magic() {
+    let reader = b"#!RADIANCE\n\n-Y 1 +X 1\n";
+    assert!(matches!(
+        radiant::read_header(&reader[..]),
+        Err(LoadError::FileFormat)
+    ));
+}
@@ -0,0 +1,33 @@
+use radiant::RGB;
+
+fn rgb(r: f32, g: f32, b: f32) -> RGB {
+    RGB { r, g, b }
+}
+
+#[test]
+fn rgb_default_is_black() {
+    assert_eq!(RGB::default(), rgb(0.0, 0.0, 0.0));
+    assert_eq!(RGB::splat(0.0), RGB::default());
+
+    // Pixels that are never decoded keep the default value
+    let partial = radiant::load_partial(&b"#?RADIANCE\n\n-Y 2 +X 1\n\xff\x00\xff\x80"[..])
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(partial.image.data[1], RGB::default());
+}
+
+#[test]
+fn rgb_splat() {
+    assert_eq!(RGB::splat(0.5), rgb(0.5, 0.5, 0.5));
+}
+
+#[test]
+fn rgb_arithmetic() {
+    let a = rgb(1.0, 2.0, 3.0);
+    let b = rgb(0.5, 0.25, 4.0);
+    assert_eq!(a + b, rgb(1.5, 2.25, 7.0));
+    assert_eq!(a - b, rgb(0.5, 1.75, -1.0));
+    assert_eq!(a * 2.0, rgb(2.0, 4.0, 6.0));
+    assert_eq!(a / 2.0, rgb(0.5, 1.0, 1.5));
+    assert_eq!((a + b) / 2.0, rgb(0.75, 1.125, 3.5));
+}
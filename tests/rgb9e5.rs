@@ -0,0 +1,59 @@
+use radiant::{rgb9e5_to_rgb, rgb_to_rgb9e5, RGB};
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+fn rgb(r: f32, g: f32, b: f32) -> RGB {
+    RGB { r, g, b }
+}
+
+#[test]
+fn rgb9e5_exact_values() {
+    let one = rgb_to_rgb9e5(RGB::splat(1.0));
+    assert_eq!(one, 256 | 256 << 9 | 256 << 18 | 16 << 27);
+    assert_eq!(rgb9e5_to_rgb(one), RGB::splat(1.0));
+
+    let mixed = rgb(0.5, 2.0, 0.0);
+    assert_eq!(rgb9e5_to_rgb(rgb_to_rgb9e5(mixed)), mixed);
+    assert_eq!(rgb_to_rgb9e5(RGB::splat(0.0)), 0);
+}
+
+#[test]
+fn rgb9e5_clamps() {
+    assert_eq!(
+        rgb9e5_to_rgb(rgb_to_rgb9e5(rgb(-1.0, 0.0, -0.0))),
+        RGB::splat(0.0)
+    );
+    assert_eq!(
+        rgb9e5_to_rgb(rgb_to_rgb9e5(rgb(1e10, f32::INFINITY, 65408.0))),
+        RGB::splat(65408.0)
+    );
+
+    let nan = rgb9e5_to_rgb(rgb_to_rgb9e5(rgb(f32::NAN, 1.0, f32::NAN)));
+    assert_eq!(nan, rgb(0.0, 1.0, 0.0));
+}
+
+#[test]
+fn rgb9e5_rounds_up_to_next_exponent() {
+    // Just below 2 rounds to a mantissa of 512, which needs a larger exponent
+    let packed = rgb_to_rgb9e5(RGB::splat(1.999));
+    assert_eq!(packed >> 27, 17);
+    assert_eq!(rgb9e5_to_rgb(packed), RGB::splat(2.0));
+}
+
+#[test]
+fn load_rgb9e5_matches_load() {
+    let (width, height, data) = radiant::load_rgb9e5(HDR).unwrap();
+    assert_eq!((width, height), (16, 4));
+
+    let image = radiant::load(HDR).unwrap();
+    assert_eq!(data.len(), image.data.len());
+    for (&packed, pixel) in data.iter().zip(&image.data) {
+        // Each channel is within half a step of the shared 9-bit scale, which is at least 2^-24
+        let max = pixel.r.max(pixel.g).max(pixel.b);
+        let tolerance = max / 256.0 + 2_f32.powi(-25);
+        let decoded = rgb9e5_to_rgb(packed);
+        assert!((decoded.r - pixel.r).abs() <= tolerance);
+        assert!((decoded.g - pixel.g).abs() <= tolerance);
+        assert!((decoded.b - pixel.b).abs() <= tolerance);
+    }
+}
@@ -0,0 +1,61 @@
+use radiant::RGB;
+
+#[test]
+fn rgb_to_rgbe_zero_and_negative() {
+    assert_eq!(radiant::rgb_to_rgbe(RGB::splat(0.0)), [0, 0, 0, 0]);
+    assert_eq!(radiant::rgb_to_rgbe(RGB::splat(-3.0)), [0, 0, 0, 0]);
+    assert_eq!(
+        radiant::rgb_to_rgbe(RGB {
+            r: -1.0,
+            g: 0.5,
+            b: 1.0,
+        }),
+        [0, 128, 255, 128]
+    );
+}
+
+#[test]
+fn rgb_to_rgbe_round_trip() {
+    for i in 0..2000 {
+        let pixel = RGB {
+            r: i as f32 * 0.37,
+            g: 1.0 / (i + 1) as f32,
+            b: (i % 17) as f32 * 1e-3,
+        };
+        let decoded = radiant::rgbe_to_rgb(radiant::rgb_to_rgbe(pixel));
+
+        // A shared exponent leaves 8 bits of precision relative to the brightest channel
+        let tolerance = pixel.r.max(pixel.g).max(pixel.b) / 256.0;
+        for (a, b) in [
+            (decoded.r, pixel.r),
+            (decoded.g, pixel.g),
+            (decoded.b, pixel.b),
+        ] {
+            assert!((a - b).abs() <= tolerance, "{:?} != {:?}", decoded, pixel);
+        }
+    }
+}
+
+#[test]
+fn rgbe_to_rgb_round_trip() {
+    // Every pixel whose brightest mantissa is above 127 survives decoding and encoding again,
+    // unless it is dark enough to be flushed to black
+    for e in 23..=255 {
+        for m in (0..=255).step_by(3) {
+            for bytes in [[m, 255, 0, e], [128, m / 2, m, e]] {
+                let rgb = radiant::rgbe_to_rgb(bytes);
+                assert_eq!(radiant::rgb_to_rgbe(rgb), bytes, "{:?}", rgb);
+            }
+        }
+    }
+}
+
+#[test]
+fn rgbe_zero_exponent_is_black() {
+    assert_eq!(radiant::rgbe_to_rgb([255, 255, 255, 0]), RGB::splat(0.0));
+    assert!(radiant::rgbe_to_rgb([255, 255, 255, 1]).r > 0.0);
+
+    let reader = b"#?RADIANCE\n\n-Y 1 +X 1\n\xff\xff\xff\x00";
+    let image = radiant::load(&reader[..]).unwrap();
+    assert_eq!(image.data, [RGB::splat(0.0)]);
+}
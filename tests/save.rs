@@ -0,0 +1,96 @@
+use radiant::{Image, SaveError, RGB};
+
+fn gradient(width: usize, height: usize) -> Image {
+    let mut data = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            data.push(RGB {
+                r: x as f32 / width as f32 * 4.0,
+                g: y as f32 / height as f32 * 0.01,
+                b: (x + y) as f32 * 100.0,
+            });
+        }
+    }
+    Image::new(width, height, data).unwrap()
+}
+
+fn assert_close(a: &[RGB], b: &[RGB]) {
+    assert_eq!(a.len(), b.len());
+    for (a, b) in a.iter().zip(b) {
+        // A shared exponent leaves 8 bits of precision relative to the brightest channel.
+        let tolerance = a.r.max(a.g).max(a.b) / 128.0;
+        assert!((a.r - b.r).abs() <= tolerance, "{:?} != {:?}", a, b);
+        assert!((a.g - b.g).abs() <= tolerance, "{:?} != {:?}", a, b);
+        assert!((a.b - b.b).abs() <= tolerance, "{:?} != {:?}", a, b);
+    }
+}
+
+#[test]
+fn save_round_trip() {
+    let image = gradient(13, 7);
+    let mut buf = Vec::new();
+    radiant::save(&image, &mut buf).unwrap();
+
+    let loaded = radiant::load(&buf[..]).unwrap();
+    assert_eq!(loaded.width, image.width);
+    assert_eq!(loaded.height, image.height);
+    assert_close(&image.data, &loaded.data);
+}
+
+#[test]
+fn encode_round_trip() {
+    let image = gradient(9, 4);
+    let bytes = radiant::encode(&image).unwrap();
+    let mut buf = Vec::new();
+    radiant::save(&image, &mut buf).unwrap();
+    assert_eq!(bytes, buf);
+
+    let loaded = radiant::load(&bytes[..]).unwrap();
+    assert_close(&image.data, &loaded.data);
+}
+
+#[test]
+fn save_exact_values() {
+    let reader = b"#?RADIANCE\0\n\n-Y 1 +X 2\n\xff\x00\xff\x80\x00\x00\x00\x00";
+    let image = radiant::load(&reader[..]).unwrap();
+    let bytes = radiant::encode(&image).unwrap();
+
+    let loaded = radiant::load(&bytes[..]).unwrap();
+    assert_eq!(loaded.data, image.data);
+}
+
+#[test]
+fn save_rle_round_trip() {
+    let image = gradient(300, 5);
+    let mut flat = Vec::new();
+    radiant::save(&image, &mut flat).unwrap();
+    let mut rle = Vec::new();
+    radiant::save_rle(&image, &mut rle).unwrap();
+    assert!(rle.len() < flat.len());
+
+    let loaded = radiant::load(&rle[..]).unwrap();
+    assert_eq!(loaded.width, image.width);
+    assert_eq!(loaded.height, image.height);
+    assert_eq!(loaded.data, radiant::load(&flat[..]).unwrap().data);
+    assert_close(&image.data, &loaded.data);
+}
+
+#[test]
+fn save_rle_narrow_falls_back_to_flat() {
+    let image = gradient(7, 3);
+    let mut flat = Vec::new();
+    radiant::save(&image, &mut flat).unwrap();
+    let mut rle = Vec::new();
+    radiant::save_rle(&image, &mut rle).unwrap();
+    assert_eq!(rle, flat);
+}
+
+#[test]
+fn save_mismatched_dimensions() {
+    let mut image = gradient(4, 4);
+    image.data.pop();
+    assert!(matches!(
+        radiant::save(&image, Vec::new()),
+        Err(SaveError::Dimensions)
+    ));
+}
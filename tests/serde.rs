@@ -0,0 +1,60 @@
+#![cfg(feature = "serde")]
+
+use radiant::{ColorFormat, Image, Orientation, RGB};
+use serde::{de::DeserializeOwned, Serialize};
+
+fn assert_serde<T: Serialize + DeserializeOwned>() {}
+
+#[test]
+fn serde_types() {
+    assert_serde::<RGB>();
+    assert_serde::<Image>();
+    assert_serde::<Orientation>();
+    assert_serde::<ColorFormat>();
+}
+
+#[test]
+fn serde_round_trip() {
+    let mut image = Image::new(
+        2,
+        1,
+        vec![
+            RGB {
+                r: 1.0,
+                g: 2.0,
+                b: 0.5,
+            },
+            RGB::splat(0.0),
+        ],
+    )
+    .unwrap();
+    image.orientation = Orientation::PosYNegX;
+    image.color_format = ColorFormat::Xyze;
+    image.set_pixel_aspect(0.5);
+
+    let value = serde_json::to_value(&image).unwrap();
+    assert_eq!(
+        value,
+        serde_json::json!({
+            "width": 2,
+            "height": 1,
+            "orientation": "PosYNegX",
+            "color_format": "Xyze",
+            "pixel_aspect": 0.5,
+            "data": [
+                { "r": 1.0, "g": 2.0, "b": 0.5 },
+                { "r": 0.0, "g": 0.0, "b": 0.0 },
+            ],
+        })
+    );
+
+    let json = serde_json::to_string(&image).unwrap();
+    let decoded: Image = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        (decoded.width, decoded.height, decoded.orientation),
+        (2, 1, Orientation::PosYNegX)
+    );
+    assert_eq!(decoded.color_format, ColorFormat::Xyze);
+    assert_eq!(decoded.pixel_aspect(), 0.5);
+    assert_eq!(decoded.data, image.data);
+}
@@ -0,0 +1,78 @@
+use radiant::{Image, RGB};
+
+fn image(data: Vec<RGB>) -> Image {
+    Image::new(data.len(), 1, data).unwrap()
+}
+
+fn grey(v: f32) -> RGB {
+    RGB { r: v, g: v, b: v }
+}
+
+#[test]
+fn reinhard_uniform_image_maps_to_key() {
+    // Every pixel is the log-average, so each maps to key / (1 + key)
+    let key = 0.18;
+    let expected = 0.18f32 / 1.18;
+    let expected = (1.055 * expected.powf(1.0 / 2.4) - 0.055) * 255.0;
+
+    for v in [0.01, 1.0, 500.0] {
+        let pixels = image(vec![grey(v); 4]).tone_map_reinhard(key);
+        for pixel in pixels {
+            for channel in pixel {
+                assert!((f32::from(channel) - expected).abs() <= 1.0);
+            }
+        }
+    }
+}
+
+#[test]
+fn reinhard_is_monotonic_and_bounded() {
+    let data = [0.0, 0.001, 0.1, 1.0, 10.0, 1000.0, 1e9].map(grey).to_vec();
+    let pixels = image(data).tone_map_reinhard(0.18);
+    assert_eq!(pixels[0], [0, 0, 0]);
+    for pair in pixels.windows(2) {
+        assert!(pair[0][1] <= pair[1][1]);
+    }
+    assert!(pixels.last().unwrap()[1] >= 254);
+}
+
+#[test]
+fn reinhard_keeps_hue() {
+    let data = vec![
+        RGB {
+            r: 1.0,
+            g: 0.0,
+            b: 0.0,
+        },
+        grey(1.0),
+    ];
+    let pixels = image(data).tone_map_reinhard(0.18);
+    assert!(pixels[0][0] > 0);
+    assert_eq!(&pixels[0][1..], &[0, 0]);
+}
+
+#[test]
+fn reinhard_empty_image() {
+    assert!(image(Vec::new()).tone_map_reinhard(0.18).is_empty());
+}
+
+#[test]
+fn rgba8_layout() {
+    let data = vec![
+        grey(0.0),
+        RGB {
+            r: 1.0,
+            g: 0.25,
+            b: 4.0,
+        },
+    ];
+    let rgba = image(data).to_rgba8(0.0, 1.0);
+    assert_eq!(rgba, [0, 0, 0, 255, 255, 64, 255, 255]);
+}
+
+#[test]
+fn rgba8_exposure_and_gamma() {
+    // 0.0625 * 2^2 = 0.25, and 0.25^(1/2) = 0.5
+    let rgba = image(vec![grey(0.0625)]).to_rgba8(2.0, 2.0);
+    assert_eq!(rgba, [128, 128, 128, 255]);
+}
@@ -0,0 +1,39 @@
+use radiant::{LoadError, ScanlineFormat};
+
+const IMAGE: &[u8] = b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y 3 +X 8\n\
+    \x02\x02\x00\x08\
+    \x84\x10\x04\x20\x21\x22\x23\x88\x00\x88\x81\x88\x80\
+    \xff\x00\xff\x80\x01\x01\x01\x07\
+    \x02\x02\x00\x08\
+    \x88\xff\x00\x88\xff\x02\x01\x02\x86\x03\x88\x80";
+
+#[test]
+fn validate_reports_scanlines() {
+    let report = radiant::validate(IMAGE).unwrap();
+    assert_eq!((report.width, report.height), (8, 3));
+    assert_eq!(
+        report.scanlines,
+        [
+            ScanlineFormat::New,
+            ScanlineFormat::Old,
+            ScanlineFormat::New
+        ]
+    );
+}
+
+#[test]
+fn validate_matches_load_errors() {
+    for len in 0..IMAGE.len() {
+        let validated = radiant::validate(&IMAGE[..len]).map(|_| ());
+        let loaded = radiant::load(&IMAGE[..len]).map(|_| ());
+        assert_eq!(format!("{:?}", validated), format!("{:?}", loaded));
+    }
+
+    let mut corrupt = IMAGE.to_vec();
+    corrupt[49] = 0x89;
+    assert!(matches!(radiant::load(&corrupt[..]), Err(LoadError::Rle)));
+    assert!(matches!(
+        radiant::validate(&corrupt[..]),
+        Err(LoadError::Rle)
+    ));
+}
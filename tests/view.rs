@@ -0,0 +1,78 @@
+use radiant::{ViewParams, ViewType};
+
+fn view(lines: &[&str]) -> Option<ViewParams> {
+    let mut bytes = b"#?RADIANCE\n".to_vec();
+    for line in lines {
+        bytes.extend_from_slice(line.as_bytes());
+        bytes.push(b'\n');
+    }
+    bytes.extend_from_slice(b"\n-Y 1 +X 1\n\xff\x00\xff\x80");
+    radiant::load_with_header(&bytes[..]).unwrap().1.view
+}
+
+#[test]
+fn view_rpict_perspective() {
+    let view = view(&[
+        "VIEW= -vtv -vp 2.5 3 1.5 -vd 0.6 -0.7 -0.2 -vu 0 0 1 -vh 60 -vv 45 -vo 0 -va 0 -vs 0 -vl 0",
+    ])
+    .unwrap();
+    assert_eq!(
+        view,
+        ViewParams {
+            view_type: ViewType::Perspective,
+            viewpoint: [2.5, 3.0, 1.5],
+            direction: [0.6, -0.7, -0.2],
+            up: [0.0, 0.0, 1.0],
+            horizontal_size: 60.0,
+            vertical_size: 45.0,
+            fore_clip: 0.0,
+            aft_clip: 0.0,
+            shift: 0.0,
+            lift: 0.0,
+            other: Vec::new(),
+        }
+    );
+}
+
+#[test]
+fn view_rpict_angular_fisheye() {
+    let view = view(&[
+        "VIEW= -vta -vp 0 0 1.2 -vd 0 0 1 -vu 0 1 0 -vh 180 -vv 180 -vo 0 -va 0 -vs 0 -vl 0",
+    ])
+    .unwrap();
+    assert_eq!(view.view_type, ViewType::Angular);
+    assert_eq!(view.direction, [0.0, 0.0, 1.0]);
+    assert_eq!(view.up, [0.0, 1.0, 0.0]);
+    assert_eq!((view.horizontal_size, view.vertical_size), (180.0, 180.0));
+}
+
+#[test]
+fn view_any_order_and_defaults() {
+    let view = view(&["VIEW= -vh 90 -vp -1 -2.5 3e1 -vth"]).unwrap();
+    assert_eq!(view.view_type, ViewType::Hemispherical);
+    assert_eq!(view.viewpoint, [-1.0, -2.5, 30.0]);
+    assert_eq!(view.horizontal_size, 90.0);
+    assert_eq!(view.vertical_size, ViewParams::default().vertical_size);
+    assert_eq!(view.direction, [0.0, 1.0, 0.0]);
+}
+
+#[test]
+fn view_lines_accumulate() {
+    let view = view(&["VIEW= -vtc -vp 1 2 3", "VIEW= -vp 4 5 6 -vs 0.25"]).unwrap();
+    assert_eq!(view.view_type, ViewType::Cylindrical);
+    assert_eq!(view.viewpoint, [4.0, 5.0, 6.0]);
+    assert_eq!(view.shift, 0.25);
+}
+
+#[test]
+fn view_unknown_options_kept() {
+    let view = view(&["VIEW= -vf cam.vf -vtx -vp 1 2 -vl 0.5 -vq"]).unwrap();
+    assert_eq!(view.other, ["-vf cam.vf", "-vtx", "-vp 1 2", "-vq"]);
+    assert_eq!(view.viewpoint, [0.0, 0.0, 0.0]);
+    assert_eq!(view.lift, 0.5);
+}
+
+#[test]
+fn view_missing() {
+    assert_eq!(view(&["SOFTWARE=test"]), None);
+}
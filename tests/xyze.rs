@@ -0,0 +1,46 @@
+use radiant::{ColorFormat, Primaries, RGB};
+
+const HDR: &[u8] = include_bytes!("../assets/gradient.hdr");
+
+/// The same scene as `HDR`, stored as CIE XYZ the way `ra_xyze` converts it.
+fn xyze_version() -> Vec<u8> {
+    let rgb = radiant::load(HDR).unwrap();
+    let matrix = Primaries::default().rgb_to_xyz();
+    let mut xyz = rgb.map(|pixel| {
+        let [r, g, b] = pixel.transform(&matrix);
+        RGB { r, g, b }
+    });
+    xyz.color_format = ColorFormat::Xyze;
+    radiant::encode(&xyz).unwrap()
+}
+
+#[test]
+fn xyze_matches_rgbe() {
+    let from_rgbe = radiant::load_rec709(HDR).unwrap();
+    let from_xyze = radiant::load_rec709(&xyze_version()[..]).unwrap();
+    assert_eq!(from_xyze.color_format, ColorFormat::Rgbe);
+    assert_eq!(from_rgbe.data.len(), from_xyze.data.len());
+
+    for (a, b) in from_xyze.data.iter().zip(&from_rgbe.data) {
+        // The XYZE file keeps 8 bits of precision relative to its brightest channel, and
+        // converting from XYZ magnifies the error
+        let tolerance = a.r.max(a.g).max(a.b).max(b.r).max(b.g).max(b.b) / 32.0;
+        for (a, b) in [(a.r, b.r), (a.g, b.g), (a.b, b.b)] {
+            assert!((a - b).abs() <= tolerance, "{} != {}", a, b);
+        }
+    }
+}
+
+#[test]
+fn xyze_kept_by_load() {
+    let bytes = xyze_version();
+    let (image, header) = radiant::load_with_header(&bytes[..]).unwrap();
+    assert_eq!(header.format, Some(ColorFormat::Xyze));
+    assert_eq!(image.color_format, ColorFormat::Xyze);
+
+    // Y is the luminance in the units of the file, without the luminous efficacy
+    let rgb = radiant::load(HDR).unwrap();
+    let (pixel, xyz) = (rgb.data[5], image.data[5]);
+    assert!((xyz.g - pixel.luminance()).abs() <= pixel.luminance() / 64.0);
+    assert_eq!(radiant::WHITE_EFFICACY, 179.0);
+}